@@ -18,6 +18,7 @@ import point2_mm_schema from "../schemas/point2_mm.schema.json";
 import transform2d_mm_schema from "../schemas/transform2d_mm.schema.json";
 import transform3d_schema from "../schemas/transform3d.schema.json";
 import violation_schema from "../schemas/violation.schema.json";
+import constraint_ruleset_schema from "../schemas/constraint_ruleset.schema.json";
 import money_schema from "../schemas/money.schema.json";
 import quote_item_schema from "../schemas/quote_item.schema.json";
 import quote_schema from "../schemas/quote.schema.json";
@@ -51,6 +52,7 @@ export function get_all_schemas(): readonly object[] {
     transform2d_mm_schema,
     transform3d_schema,
     violation_schema,
+    constraint_ruleset_schema,
     money_schema,
     quote_item_schema,
     quote_schema,
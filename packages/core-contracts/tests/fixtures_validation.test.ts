@@ -28,14 +28,18 @@ describe("fixtures validate against schemas", () => {
     const ajv = create_ajv();
     const basic = await read_json("./fixtures/room_basic.fixture.json");
     const detailed = await read_json("./fixtures/room_with_openings_utilities.fixture.json");
+    const l_shaped = await read_json("./fixtures/room_l_shaped.fixture.json");
 
     const res_basic = validate_with_ajv(ajv, "planforge://schemas/room.schema.json", basic);
     const res_detailed = validate_with_ajv(ajv, "planforge://schemas/room.schema.json", detailed);
+    const res_l_shaped = validate_with_ajv(ajv, "planforge://schemas/room.schema.json", l_shaped);
 
     expect(res_basic.ok).toBe(true);
     expect(res_basic.errors).toEqual([]);
     expect(res_detailed.ok).toBe(true);
     expect(res_detailed.errors).toEqual([]);
+    expect(res_l_shaped.ok).toBe(true);
+    expect(res_l_shaped.errors).toEqual([]);
   });
 
   test("Proposal fixtures are valid", async () => {
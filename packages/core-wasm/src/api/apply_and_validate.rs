@@ -0,0 +1,42 @@
+use crate::api::apply_patch::apply;
+use crate::api::validate_layout::validate_layout;
+use crate::api::violation_delta::violation_delta;
+use crate::model::kitchen_state::KitchenState;
+use crate::model::violation::Violation;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+fn violations_response(violations: Vec<Violation>) -> String {
+    serde_json::to_string(&json!({ "violations": violations })).unwrap_or_else(|_| "{\"violations\":[]}".to_string())
+}
+
+/// Applies `patch_json` and validates the result in one call. Returns the patched state, its
+/// violations and the delta against the pre-patch state. Patch failures use the same response
+/// as `apply_patch_json`.
+pub fn apply_and_validate_json(kitchen_state_json: String, patch_json: String) -> String {
+    let applied = match apply(&kitchen_state_json, &patch_json) {
+        Ok(applied) => applied,
+        Err(response) => return response,
+    };
+
+    let before: KitchenState = match serde_json::from_value(applied.before) {
+        Ok(value) => value,
+        Err(err) => {
+            let mut details = HashMap::new();
+            details.insert("message".to_string(), Value::String(err.to_string()));
+            return violations_response(vec![
+                Violation::error("json.parse_error", "Invalid KitchenState JSON", vec![]).with_details(details),
+            ]);
+        }
+    };
+
+    let before_violations = validate_layout(&before);
+    let violations = validate_layout(&applied.kitchen_state);
+
+    serde_json::to_string(&json!({
+        "kitchen_state": applied.kitchen_state,
+        "violations": violations,
+        "delta": violation_delta(&before_violations, &violations),
+    }))
+    .unwrap_or_else(|_| "{}".to_string())
+}
@@ -1,4 +1,5 @@
 use crate::model::kitchen_state::KitchenState;
+use crate::model::layout::LayoutObject;
 use crate::model::render_model::{Assets, GltfAssetRef, Quat, RenderModel, RenderNode, Transform3D, Vec3};
 use crate::model::violation::Violation;
 use serde_json::json;
@@ -20,45 +21,63 @@ pub fn derive_render_model_json(kitchen_state_json: String, quality: String) ->
         }
     };
 
-    let mut gltf_assets: HashMap<String, GltfAssetRef> = HashMap::new();
-    let mut nodes: Vec<RenderNode> = Vec::new();
+    serde_json::to_string(&derive_render_model(&kitchen_state, &quality)).unwrap_or_else(|_| "{}".to_string())
+}
 
-    for obj in &kitchen_state.layout.objects {
-        let gltf_key = obj.catalog_item_id.clone();
-        gltf_assets.entry(gltf_key.clone()).or_insert_with(|| {
-            let lod = if quality == "quality" { 0 } else { 1 };
-            GltfAssetRef {
-                asset_id: format!("asset_{}", gltf_key),
-                uri: format!("assets/models/{}/lod{}.glb", gltf_key, lod),
-            }
-        });
+fn lod_for(quality: &str) -> u32 {
+    if quality == "quality" {
+        0
+    } else {
+        1
+    }
+}
 
-        let x_m = obj.transform_mm.position_mm.x as f64 / 1000.0;
-        let z_m = obj.transform_mm.position_mm.y as f64 / 1000.0;
+pub fn gltf_asset_ref(gltf_key: &str, quality: &str) -> GltfAssetRef {
+    GltfAssetRef {
+        asset_id: format!("asset_{}", gltf_key),
+        uri: format!("assets/models/{}/lod{}.glb", gltf_key, lod_for(quality)),
+    }
+}
 
-        let transform = Transform3D {
-            position_m: Vec3 { x: x_m, y: 0.0, z: z_m },
-            rotation_quat: Quat { x: 0.0, y: 0.0, z: 0.0, w: 1.0 },
-            scale: Vec3 { x: 1.0, y: 1.0, z: 1.0 },
-        };
+pub fn render_transform(obj: &LayoutObject) -> Transform3D {
+    let x_m = obj.transform_mm.position_mm.x as f64 / 1000.0;
+    let y_m = obj.transform_mm.elevation() as f64 / 1000.0;
+    let z_m = obj.transform_mm.position_mm.y as f64 / 1000.0;
 
-        nodes.push(RenderNode {
-            id: format!("node_{}", obj.id),
-            source_object_id: obj.id.clone(),
-            gltf_key,
-            transform,
-            material_overrides: obj.material_slots.clone(),
-            lod: Some(if quality == "quality" { 0 } else { 1 }),
-            pickable: Some(true),
-        });
+    Transform3D {
+        position_m: Vec3 { x: x_m, y: y_m, z: z_m },
+        rotation_quat: Quat { x: 0.0, y: 0.0, z: 0.0, w: 1.0 },
+        scale: Vec3 { x: 1.0, y: 1.0, z: 1.0 },
     }
+}
 
-    let render_model = RenderModel {
-        schema_version: kitchen_state.schema_version,
+pub fn render_node(obj: &LayoutObject, quality: &str) -> RenderNode {
+    RenderNode {
+        id: format!("node_{}", obj.id),
+        source_object_id: obj.id.clone(),
+        gltf_key: obj.catalog_item_id.clone(),
+        transform: render_transform(obj),
+        material_overrides: obj.material_slots.clone(),
+        lod: Some(lod_for(quality)),
+        pickable: Some(true),
+    }
+}
+
+pub fn derive_render_model(kitchen_state: &KitchenState, quality: &str) -> RenderModel {
+    let mut gltf_assets: HashMap<String, GltfAssetRef> = HashMap::new();
+    let mut nodes: Vec<RenderNode> = Vec::new();
+
+    for obj in &kitchen_state.layout.objects {
+        gltf_assets
+            .entry(obj.catalog_item_id.clone())
+            .or_insert_with(|| gltf_asset_ref(&obj.catalog_item_id, quality));
+        nodes.push(render_node(obj, quality));
+    }
+
+    RenderModel {
+        schema_version: kitchen_state.schema_version.clone(),
         assets: Assets { gltf: gltf_assets },
         nodes,
         extensions: None,
-    };
-
-    serde_json::to_string(&render_model).unwrap_or_else(|_| "{}".to_string())
+    }
 }
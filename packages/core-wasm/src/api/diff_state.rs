@@ -0,0 +1,37 @@
+use crate::model::kitchen_state::KitchenState;
+use crate::model::patch::ProposedPatch;
+use crate::model::violation::Violation;
+use crate::patch::diff::diff;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+fn violations_response(violations: Vec<Violation>) -> String {
+    serde_json::to_string(&json!({ "violations": violations })).unwrap_or_else(|_| "{\"violations\":[]}".to_string())
+}
+
+fn parse_state(kitchen_state_json: &str, message: &str) -> Result<Value, Vec<Violation>> {
+    let kitchen_state: KitchenState = serde_json::from_str(kitchen_state_json).map_err(|err| {
+        let mut details = HashMap::new();
+        details.insert("message".to_string(), Value::String(err.to_string()));
+        vec![Violation::error("json.parse_error", message, vec![]).with_details(details)]
+    })?;
+    serde_json::to_value(&kitchen_state).map_err(|_| vec![Violation::error("json.parse_error", message, vec![])])
+}
+
+pub fn diff_state_json(before_json: String, after_json: String) -> String {
+    let before = parse_state(&before_json, "Invalid before KitchenState JSON");
+    let after = parse_state(&after_json, "Invalid after KitchenState JSON");
+    let (before, after) = match (before, after) {
+        (Ok(before), Ok(after)) => (before, after),
+        (before, after) => {
+            return violations_response(before.err().into_iter().chain(after.err()).flatten().collect());
+        }
+    };
+
+    let patch = ProposedPatch {
+        ops: diff(&before, &after),
+        reason: None,
+        source: None,
+    };
+    serde_json::to_string(&patch).unwrap_or_else(|_| "{\"ops\":[]}".to_string())
+}
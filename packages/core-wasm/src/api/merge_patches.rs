@@ -0,0 +1,54 @@
+use crate::model::kitchen_state::KitchenState;
+use crate::model::patch::ProposedPatch;
+use crate::model::violation::Violation;
+use crate::patch::merge::merge;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+fn violations_response(violations: Vec<Violation>) -> String {
+    serde_json::to_string(&json!({ "violations": violations })).unwrap_or_else(|_| "{\"violations\":[]}".to_string())
+}
+
+fn parse_error(message: &str, err: serde_json::Error) -> Violation {
+    let mut details = HashMap::new();
+    details.insert("message".to_string(), Value::String(err.to_string()));
+    Violation::error("json.parse_error", message, vec![]).with_details(details)
+}
+
+/// Rebases `patch_b_json` onto `patch_a_json`, both authored against `base_json`.
+/// Returns `{ kitchen_state, rebased_patch, conflicts }`; conflicting edits keep patch A's value.
+pub fn merge_patches_json(base_json: String, patch_a_json: String, patch_b_json: String) -> String {
+    let base: KitchenState = match serde_json::from_str(&base_json) {
+        Ok(value) => value,
+        Err(err) => return violations_response(vec![parse_error("Invalid KitchenState JSON", err)]),
+    };
+    let patch_a: ProposedPatch = match serde_json::from_str(&patch_a_json) {
+        Ok(value) => value,
+        Err(err) => return violations_response(vec![parse_error("Invalid patch A JSON", err)]),
+    };
+    let patch_b: ProposedPatch = match serde_json::from_str(&patch_b_json) {
+        Ok(value) => value,
+        Err(err) => return violations_response(vec![parse_error("Invalid patch B JSON", err)]),
+    };
+
+    let base_value = match serde_json::to_value(&base) {
+        Ok(value) => value,
+        Err(err) => return violations_response(vec![parse_error("Invalid KitchenState JSON", err)]),
+    };
+    let outcome = match merge(&base_value, &patch_a, &patch_b) {
+        Ok(outcome) => outcome,
+        Err(violations) => return violations_response(violations),
+    };
+
+    let kitchen_state: KitchenState = match serde_json::from_value(outcome.merged) {
+        Ok(value) => value,
+        Err(err) => return violations_response(vec![parse_error("Merged KitchenState invalid", err)]),
+    };
+
+    serde_json::to_string(&json!({
+        "kitchen_state": kitchen_state,
+        "rebased_patch": outcome.rebased,
+        "conflicts": outcome.conflicts,
+    }))
+    .unwrap_or_else(|_| "{}".to_string())
+}
@@ -1,5 +1,11 @@
+pub mod apply_and_validate;
 pub mod apply_patch;
 pub mod derive_render_model;
+pub mod diff_state;
+pub mod merge_patches;
 pub mod normalize_state;
+pub mod proposal_metrics;
 pub mod room_metrics;
+pub mod session;
 pub mod validate_layout;
+pub mod violation_delta;
@@ -1,3 +1,4 @@
+use crate::constraints::outline::build_room_outline;
 use crate::model::kitchen_state::KitchenState;
 use crate::model::violation::Violation;
 use serde_json::json;
@@ -7,14 +8,6 @@ fn violations_response(violations: Vec<Violation>) -> String {
     serde_json::to_string(&json!({ "violations": violations })).unwrap_or_else(|_| "{\"violations\":[]}".to_string())
 }
 
-fn wall_length(room: &crate::model::room::SizeMm, wall_id: &str) -> Option<i32> {
-    match wall_id {
-        "north" | "south" => Some(room.width),
-        "east" | "west" => Some(room.depth),
-        _ => None,
-    }
-}
-
 pub fn normalize_state_json(kitchen_state_json: String) -> String {
     let mut kitchen_state: KitchenState = match serde_json::from_str(&kitchen_state_json) {
         Ok(value) => value,
@@ -27,11 +20,11 @@ pub fn normalize_state_json(kitchen_state_json: String) -> String {
         }
     };
 
-    let room = &kitchen_state.room.size_mm;
+    let outline = build_room_outline(&kitchen_state.room);
 
     kitchen_state.room.openings.sort_by(|a, b| a.id.cmp(&b.id));
     for opening in &mut kitchen_state.room.openings {
-        if let Some(length) = wall_length(room, &opening.wall_id) {
+        if let Some(length) = outline.wall_length_mm(&opening.wall_id) {
             let max_offset = (length - opening.width_mm).max(0);
             if opening.offset_mm < 0 {
                 opening.offset_mm = 0;
@@ -52,7 +45,7 @@ pub fn normalize_state_json(kitchen_state_json: String) -> String {
             util.zone_radius_mm = 0;
         }
         if let (Some(wall_id), Some(offset)) = (&util.wall_id, util.offset_mm) {
-            if let Some(length) = wall_length(room, wall_id) {
+            if let Some(length) = outline.wall_length_mm(wall_id) {
                 let clamped = offset.max(0).min(length.max(0));
                 util.offset_mm = Some(clamped);
             }
@@ -0,0 +1,166 @@
+use crate::constraints::clearances::passages;
+use crate::constraints::openings::DoorClearance;
+use crate::constraints::outline::build_room_outline;
+use crate::constraints::plumbing_power::{distance_mm, utility_points};
+use crate::constraints::ruleset::RulesetCatalog;
+use crate::constraints::{
+    build_footprints, has_tag, Constraint, ConstraintContext, Footprint, ValidationScope,
+};
+use crate::model::kitchen_state::KitchenState;
+use crate::model::proposal_metrics::ProposalMetrics;
+use crate::model::room::UtilityKind;
+use crate::model::violation::Violation;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+const SINK_TAGS: &[&str] = &["sink"];
+const HOB_TAGS: &[&str] = &["hob", "cooktop"];
+const FRIDGE_TAGS: &[&str] = &["fridge", "refrigerator"];
+/// Walkways wider than this multiple of the ruleset's minimum passage are not measured.
+const PASSAGE_SEARCH_FACTOR: i32 = 2;
+
+fn metrics_response(metrics: Option<ProposalMetrics>, violations: Vec<Violation>) -> String {
+    serde_json::to_string(&json!({ "metrics": metrics, "violations": violations }))
+        .unwrap_or_else(|_| "{\"metrics\":null,\"violations\":[]}".to_string())
+}
+
+/// Returns `{ metrics, violations }`. `metrics` is null when the state cannot be parsed or its
+/// ruleset is unknown; an incomplete work triangle is reported as an info violation.
+pub fn compute_proposal_metrics_json(kitchen_state_json: String) -> String {
+    let kitchen_state: KitchenState = match serde_json::from_str(&kitchen_state_json) {
+        Ok(value) => value,
+        Err(err) => {
+            let mut details = HashMap::new();
+            details.insert("message".to_string(), Value::String(err.to_string()));
+            return metrics_response(
+                None,
+                vec![Violation::error("json.parse_error", "Invalid KitchenState JSON", vec![]).with_details(details)],
+            );
+        }
+    };
+
+    match compute_proposal_metrics(&kitchen_state, &RulesetCatalog::builtin()) {
+        Ok((metrics, violations)) => metrics_response(Some(metrics), violations),
+        Err(violations) => metrics_response(None, violations),
+    }
+}
+
+pub fn compute_proposal_metrics(
+    kitchen_state: &KitchenState,
+    rulesets: &RulesetCatalog,
+) -> Result<(ProposalMetrics, Vec<Violation>), Vec<Violation>> {
+    let rules = rulesets.resolve(kitchen_state.project.ruleset_version.as_deref())?;
+    let footprints = build_footprints(&kitchen_state.layout.objects);
+    let outline = build_room_outline(&kitchen_state.room);
+    let ctx = ConstraintContext {
+        state: kitchen_state,
+        footprints: &footprints,
+        outline: &outline,
+        scope: &ValidationScope::All,
+        rules,
+    };
+
+    let mut violations = Vec::new();
+    let triangle_edges_mm = work_triangle(&footprints, &mut violations);
+    let mut door_violations = Vec::new();
+    DoorClearance.evaluate(&ctx, &mut door_violations);
+
+    let metrics = ProposalMetrics {
+        schema_version: kitchen_state.schema_version.clone(),
+        triangle_proxy_mm: triangle_edges_mm.map(|edges| edges.iter().sum()).unwrap_or(0),
+        triangle_edges_mm,
+        min_passage_mm: min_passage_mm(&ctx),
+        door_clearance_ok: door_violations.is_empty(),
+        utility_fit_score: utility_fit_score(&ctx),
+    };
+    Ok((metrics, violations))
+}
+
+/// Sink–hob, hob–fridge and fridge–sink distances between footprint centres. Each corner is the
+/// first object in layout order carrying a matching tag.
+fn work_triangle(footprints: &[Footprint], violations: &mut Vec<Violation>) -> Option<[i32; 3]> {
+    let find = |tags: &[&str]| footprints.iter().find(|fp| has_tag(&fp.tags, tags));
+    let (sink, hob, fridge) = (find(SINK_TAGS), find(HOB_TAGS), find(FRIDGE_TAGS));
+    let (Some(sink), Some(hob), Some(fridge)) = (sink, hob, fridge) else {
+        let missing: Vec<Value> = [("sink", sink), ("hob", hob), ("fridge", fridge)]
+            .into_iter()
+            .filter(|(_, fp)| fp.is_none())
+            .map(|(role, _)| Value::String(role.to_string()))
+            .collect();
+        let mut details = HashMap::new();
+        details.insert("missing".to_string(), Value::Array(missing));
+        violations.push(
+            Violation::info(
+                "metrics.work_triangle_incomplete",
+                "layout has no sink, hob or fridge to measure the work triangle",
+                vec![],
+            )
+            .with_details(details),
+        );
+        return None;
+    };
+
+    let edge = |a: &Footprint, b: &Footprint| (a.obb.center - b.obb.center).length().round() as i32;
+    Some([edge(sink, hob), edge(hob, fridge), edge(fridge, sink)])
+}
+
+/// Narrowest walkway between objects or between an object and a wall, or the room's smaller
+/// extent when there is none. `passages` is exact below its bound, so the search starts at
+/// `PASSAGE_SEARCH_FACTOR` times the ruleset's minimum passage and doubles until it finds one.
+fn min_passage_mm(ctx: &ConstraintContext) -> i32 {
+    let bounds = ctx.outline.bounds();
+    let room_extent = (bounds.max_x - bounds.min_x).min(bounds.max_y - bounds.min_y).max(0);
+    let mut search_mm = ctx.rules.min_passage_mm.saturating_mul(PASSAGE_SEARCH_FACTOR).max(1);
+    loop {
+        search_mm = search_mm.min(room_extent);
+        let narrowest = passages(ctx, search_mm)
+            .into_iter()
+            .map(|passage| passage.width_mm.round() as i32)
+            .min();
+        match narrowest {
+            Some(width) => return width.min(room_extent),
+            None if search_mm >= room_extent => return room_extent,
+            None => search_mm = search_mm.saturating_mul(2),
+        }
+    }
+}
+
+/// Mean fit of sinks to water/drain points and hobs to vents. An object within a utility's zone
+/// scores 1, one further away `zone_radius / distance`, and one with no such utility 0. A layout
+/// without sinks or hobs scores 1.
+fn utility_fit_score(ctx: &ConstraintContext) -> f64 {
+    let water = utility_points(ctx, &[UtilityKind::Water, UtilityKind::Drain]);
+    let vents = utility_points(ctx, &[UtilityKind::Vent]);
+
+    let fits: Vec<f64> = ctx
+        .footprints
+        .iter()
+        .filter_map(|fp| {
+            let targets = if has_tag(&fp.tags, SINK_TAGS) {
+                &water
+            } else if has_tag(&fp.tags, HOB_TAGS) {
+                &vents
+            } else {
+                return None;
+            };
+            let fit = targets
+                .iter()
+                .map(|(pos, radius)| {
+                    let distance = distance_mm(&fp.anchor, pos);
+                    if distance <= *radius as f64 {
+                        1.0
+                    } else {
+                        (*radius).max(0) as f64 / distance
+                    }
+                })
+                .fold(0.0, f64::max);
+            Some(fit)
+        })
+        .collect();
+
+    if fits.is_empty() {
+        return 1.0;
+    }
+    let mean = fits.iter().sum::<f64>() / fits.len() as f64;
+    (mean * 1000.0).round() / 1000.0
+}
@@ -1,15 +1,8 @@
 use crate::constraints::build_footprints;
+use crate::constraints::outline::build_room_outline;
 use crate::model::kitchen_state::KitchenState;
 use serde_json::json;
 
-fn wall_length(room: &crate::model::room::SizeMm, wall_id: &str) -> Option<i32> {
-    match wall_id {
-        "north" | "south" => Some(room.width),
-        "east" | "west" => Some(room.depth),
-        _ => None,
-    }
-}
-
 pub fn compute_room_metrics_json(kitchen_state_json: String) -> String {
     let kitchen_state: KitchenState = match serde_json::from_str(&kitchen_state_json) {
         Ok(value) => value,
@@ -18,13 +11,18 @@ pub fn compute_room_metrics_json(kitchen_state_json: String) -> String {
         }
     };
 
-    let room = &kitchen_state.room.size_mm;
-    let room_area = (room.width as i64).max(0) * (room.depth as i64).max(0);
-    let room_perimeter = ((room.width + room.depth) as i64 * 2).max(0);
+    serde_json::to_string(&json!({ "metrics": compute_room_metrics(&kitchen_state) }))
+        .unwrap_or_else(|_| "{\"metrics\":null}".to_string())
+}
+
+pub fn compute_room_metrics(kitchen_state: &KitchenState) -> serde_json::Value {
+    let outline = build_room_outline(&kitchen_state.room);
+    let room_area = outline.polygon.area_mm2().round() as i64;
+    let room_perimeter = outline.polygon.perimeter_mm().round() as i64;
     let footprints = build_footprints(&kitchen_state.layout.objects);
     let mut occupied_area: i64 = 0;
     for fp in &footprints {
-        occupied_area += fp.aabb.area_mm2();
+        occupied_area += fp.obb.area_mm2().round() as i64;
     }
 
     let coverage_ratio = if room_area > 0 {
@@ -34,27 +32,24 @@ pub fn compute_room_metrics_json(kitchen_state_json: String) -> String {
     };
 
     let mut wall_available: serde_json::Map<String, serde_json::Value> = serde_json::Map::new();
-    for wall in ["north", "south", "east", "west"] {
-        let length = wall_length(room, wall).unwrap_or(0).max(0);
+    for wall in &outline.walls {
+        let length = wall.length_mm().round() as i64;
         let mut blocked: i64 = 0;
         for opening in &kitchen_state.room.openings {
-            if opening.wall_id == wall {
+            if opening.wall_id == wall.id {
                 blocked += opening.width_mm.max(0) as i64;
             }
         }
-        let available = (length as i64 - blocked).max(0);
-        wall_available.insert(wall.to_string(), json!(available));
+        let available = (length - blocked).max(0);
+        wall_available.insert(wall.id.clone(), json!(available));
     }
 
-    serde_json::to_string(&json!({
-        "metrics": {
-            "room_area_mm2": room_area,
-            "occupied_area_mm2": occupied_area,
-            "coverage_ratio": coverage_ratio,
-            "object_count": footprints.len(),
-            "room_perimeter_mm": room_perimeter,
-            "wall_available_mm": wall_available,
-        }
-    }))
-    .unwrap_or_else(|_| "{\"metrics\":null}".to_string())
+    json!({
+        "room_area_mm2": room_area,
+        "occupied_area_mm2": occupied_area,
+        "coverage_ratio": coverage_ratio,
+        "object_count": footprints.len(),
+        "room_perimeter_mm": room_perimeter,
+        "wall_available_mm": wall_available,
+    })
 }
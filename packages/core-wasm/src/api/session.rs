@@ -0,0 +1,236 @@
+use crate::api::derive_render_model::{derive_render_model, gltf_asset_ref, render_node, render_transform};
+use crate::api::room_metrics::compute_room_metrics;
+use crate::api::validate_layout::{validate_layout, validate_layout_scoped};
+use crate::constraints::ValidationScope;
+use crate::model::kitchen_state::KitchenState;
+use crate::model::layout::LayoutObject;
+use crate::model::patch::ProposedPatch;
+use crate::model::render_model::RenderModel;
+use crate::model::violation::Violation;
+use crate::patch::{apply_patch_in_place, pointer};
+use serde_json::{json, Value};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use wasm_bindgen::prelude::*;
+
+fn violations_response(violations: Vec<Violation>) -> String {
+    serde_json::to_string(&json!({ "violations": violations })).unwrap_or_else(|_| "{\"violations\":[]}".to_string())
+}
+
+fn not_loaded() -> String {
+    violations_response(vec![Violation::error(
+        "session.not_loaded",
+        "no KitchenState loaded into the session",
+        vec![],
+    )])
+}
+
+fn parse_error(message: &str, err: serde_json::Error) -> Violation {
+    let mut details = HashMap::new();
+    details.insert("message".to_string(), Value::String(err.to_string()));
+    Violation::error("json.parse_error", message, vec![]).with_details(details)
+}
+
+/// What a patch touched, read from the concrete paths of its inverse ops.
+#[derive(Default)]
+struct TouchedPaths {
+    /// Indices of layout objects edited in place.
+    objects: BTreeSet<usize>,
+    /// Objects were added, removed or reordered.
+    object_list: bool,
+    /// Something outside `/layout/objects` changed.
+    other: bool,
+}
+
+fn touched_paths(inverse: &ProposedPatch) -> TouchedPaths {
+    let mut touched = TouchedPaths::default();
+    let paths = inverse.ops.iter().flat_map(|op| std::iter::once(&op.path).chain(op.from.as_ref()));
+    for path in paths {
+        let tokens = pointer::parse_pointer(path).unwrap_or_default();
+        match tokens.as_slice() {
+            [layout, objects, index, _, ..] if layout == "layout" && objects == "objects" => {
+                match index.parse::<usize>() {
+                    Ok(index) => {
+                        touched.objects.insert(index);
+                    }
+                    Err(_) => touched.object_list = true,
+                }
+            }
+            [layout, objects, ..] if layout == "layout" && objects == "objects" => touched.object_list = true,
+            _ => touched.other = true,
+        }
+    }
+    touched
+}
+
+/// Keeps a parsed `KitchenState` in wasm memory so interactive edits (dragging, patching) and
+/// the derived views built from them do not re-parse the whole state on every call.
+#[wasm_bindgen]
+#[derive(Default)]
+pub struct PlanSession {
+    state: Option<KitchenState>,
+    /// JSON form of `state`, kept in sync so patches apply without re-serializing the state.
+    doc: Option<Value>,
+    render_cache: Option<(String, RenderModel)>,
+}
+
+#[wasm_bindgen]
+impl PlanSession {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> PlanSession {
+        PlanSession::default()
+    }
+
+    /// Replaces the session state. Returns `{ "violations": [] }` on success.
+    pub fn load(&mut self, kitchen_state_json: String) -> String {
+        match serde_json::from_str::<KitchenState>(&kitchen_state_json) {
+            Ok(state) => {
+                self.doc = serde_json::to_value(&state).ok();
+                self.state = Some(state);
+                self.render_cache = None;
+                violations_response(vec![])
+            }
+            Err(err) => violations_response(vec![parse_error("Invalid KitchenState JSON", err)]),
+        }
+    }
+
+    pub fn state_json(&self) -> String {
+        match &self.state {
+            Some(state) => serde_json::to_string(state).unwrap_or_else(|_| "{}".to_string()),
+            None => not_loaded(),
+        }
+    }
+
+    /// Applies a patch atomically. Returns `{ "violations": [], "inverse_patch" }` on success; on
+    /// failure the state is unchanged and the violations name the failing op.
+    pub fn apply_patch(&mut self, patch_json: String) -> String {
+        let (Some(state), Some(doc)) = (&mut self.state, &mut self.doc) else {
+            return not_loaded();
+        };
+        let patch: ProposedPatch = match serde_json::from_str(&patch_json) {
+            Ok(value) => value,
+            Err(err) => return violations_response(vec![parse_error("Invalid patch JSON", err)]),
+        };
+        let inverse = match apply_patch_in_place(doc, &patch) {
+            Ok(inverse) => inverse,
+            Err(violations) => return violations_response(violations),
+        };
+
+        let touched = touched_paths(&inverse);
+        if let Err(err) = sync_state(state, doc, &touched) {
+            let _ = apply_patch_in_place(doc, &inverse);
+            return violations_response(vec![parse_error("Patched KitchenState invalid", err)]);
+        }
+        if touched.other || touched.object_list {
+            self.render_cache = None;
+        } else if let Some((quality, model)) = &mut self.render_cache {
+            for &index in &touched.objects {
+                let (Some(obj), Some(node)) = (state.layout.objects.get(index), model.nodes.get_mut(index)) else {
+                    continue;
+                };
+                *node = render_node(obj, quality);
+                model
+                    .assets
+                    .gltf
+                    .entry(obj.catalog_item_id.clone())
+                    .or_insert_with(|| gltf_asset_ref(&obj.catalog_item_id, quality));
+            }
+        }
+        serde_json::to_string(&json!({ "violations": [], "inverse_patch": inverse }))
+            .unwrap_or_else(|_| "{\"violations\":[]}".to_string())
+    }
+
+    /// Drag fast path: moves one object without going through JSON Patch and updates only its
+    /// render node.
+    pub fn set_object_position(&mut self, object_id: String, x_mm: i32, y_mm: i32) -> String {
+        let Some(state) = &mut self.state else {
+            return not_loaded();
+        };
+        let Some(obj) = state.layout.objects.iter_mut().find(|o| o.id == object_id) else {
+            return violations_response(vec![Violation::error(
+                "session.unknown_object",
+                "layout object not found",
+                vec![object_id],
+            )]);
+        };
+        obj.transform_mm.position_mm.x = x_mm;
+        obj.transform_mm.position_mm.y = y_mm;
+        if let Some(position) = self
+            .doc
+            .as_mut()
+            .and_then(|doc| doc.pointer_mut("/layout/objects"))
+            .and_then(Value::as_array_mut)
+            .and_then(|objects| objects.iter_mut().find(|o| o["id"] == object_id.as_str()))
+            .and_then(|o| o.pointer_mut("/transform_mm/position_mm"))
+        {
+            position["x"] = json!(x_mm);
+            position["y"] = json!(y_mm);
+        }
+
+        if let Some((_, model)) = &mut self.render_cache {
+            if let Some(node) = model.nodes.iter_mut().find(|n| n.source_object_id == object_id) {
+                node.transform = render_transform(obj);
+            }
+        }
+        violations_response(vec![])
+    }
+
+    pub fn validate(&self) -> String {
+        match &self.state {
+            Some(state) => violations_response(validate_layout(state)),
+            None => not_loaded(),
+        }
+    }
+
+    /// Drag-mode validation limited to the objects in `object_ids_json` (a JSON array of ids).
+    pub fn validate_objects(&self, object_ids_json: String) -> String {
+        let Some(state) = &self.state else {
+            return not_loaded();
+        };
+        match serde_json::from_str::<HashSet<String>>(&object_ids_json) {
+            Ok(ids) => violations_response(validate_layout_scoped(state, &ValidationScope::Objects(ids))),
+            Err(err) => violations_response(vec![parse_error("Invalid object ids JSON", err)]),
+        }
+    }
+
+    pub fn room_metrics(&self) -> String {
+        match &self.state {
+            Some(state) => serde_json::to_string(&json!({ "metrics": compute_room_metrics(state) }))
+                .unwrap_or_else(|_| "{\"metrics\":null}".to_string()),
+            None => not_loaded(),
+        }
+    }
+
+    /// Render model for the current state, rebuilt only after patches or a quality change.
+    pub fn render_model(&mut self, quality: String) -> String {
+        let Some(state) = &self.state else {
+            return not_loaded();
+        };
+        let stale = !matches!(&self.render_cache, Some((cached, _)) if *cached == quality);
+        if stale {
+            let model = derive_render_model(state, &quality);
+            self.render_cache = Some((quality, model));
+        }
+        match &self.render_cache {
+            Some((_, model)) => serde_json::to_string(model).unwrap_or_else(|_| "{}".to_string()),
+            None => "{}".to_string(),
+        }
+    }
+}
+
+/// Brings the typed state up to date with `doc` after a patch, deserializing only what it touched.
+fn sync_state(state: &mut KitchenState, doc: &Value, touched: &TouchedPaths) -> Result<(), serde_json::Error> {
+    if touched.other {
+        *state = serde_json::from_value(doc.clone())?;
+    } else if touched.object_list {
+        state.layout.objects = serde_json::from_value(doc["layout"]["objects"].clone())?;
+    } else {
+        let mut updated: Vec<(usize, LayoutObject)> = Vec::with_capacity(touched.objects.len());
+        for &index in &touched.objects {
+            updated.push((index, serde_json::from_value(doc["layout"]["objects"][index].clone())?));
+        }
+        for (index, obj) in updated {
+            state.layout.objects[index] = obj;
+        }
+    }
+    Ok(())
+}
@@ -1,6 +1,9 @@
-use crate::constraints::validate_constraints;
+use crate::constraints::outline::build_room_outline;
+use crate::constraints::ruleset::RulesetCatalog;
+use crate::constraints::{build_footprints, validate_constraints_with, ValidationScope};
 use crate::model::kitchen_state::KitchenState;
 use crate::model::violation::Violation;
+use crate::plugins::registry::PluginRegistry;
 use serde_json::json;
 use std::collections::HashSet;
 
@@ -20,28 +23,129 @@ pub fn validate_layout_json(kitchen_state_json: String) -> String {
         }
     };
 
-    let mut violations: Vec<Violation> = Vec::new();
+    violations_response(validate_layout(&kitchen_state))
+}
 
-    if kitchen_state.schema_version.trim().is_empty() {
-        violations.push(Violation::error(
-            "schema.empty_version",
-            "schema_version must be set",
-            vec![],
-        ));
+/// Like `validate_layout_json`, with `rulesets_json` (a JSON array of rulesets) added to the
+/// built-in rulesets; entries replace built-ins of the same version.
+pub fn validate_layout_with_rulesets_json(kitchen_state_json: String, rulesets_json: String) -> String {
+    let kitchen_state: KitchenState = match serde_json::from_str(&kitchen_state_json) {
+        Ok(value) => value,
+        Err(err) => {
+            let mut details = std::collections::HashMap::new();
+            details.insert("message".to_string(), serde_json::Value::String(err.to_string()));
+            return violations_response(vec![
+                Violation::error("json.parse_error", "Invalid KitchenState JSON", vec![]).with_details(details),
+            ]);
+        }
+    };
+    let mut rulesets = RulesetCatalog::builtin();
+    match RulesetCatalog::from_json(&rulesets_json) {
+        Ok(extra) => rulesets.extend(extra),
+        Err(err) => {
+            let mut details = std::collections::HashMap::new();
+            details.insert("message".to_string(), serde_json::Value::String(err.to_string()));
+            return violations_response(vec![
+                Violation::error("json.parse_error", "Invalid rulesets JSON", vec![]).with_details(details),
+            ]);
+        }
     }
 
+    violations_response(validate_layout_with(
+        &kitchen_state,
+        &ValidationScope::All,
+        &rulesets,
+        &PluginRegistry::with_builtin(),
+    ))
+}
+
+/// Validates only what involves `object_ids_json` (a JSON array of layout object ids), for use
+/// while objects are being dragged. Violations match the full run for those objects.
+pub fn validate_drag_json(kitchen_state_json: String, object_ids_json: String) -> String {
+    let kitchen_state: KitchenState = match serde_json::from_str(&kitchen_state_json) {
+        Ok(value) => value,
+        Err(err) => {
+            let mut details = std::collections::HashMap::new();
+            details.insert("message".to_string(), serde_json::Value::String(err.to_string()));
+            return violations_response(vec![
+                Violation::error("json.parse_error", "Invalid KitchenState JSON", vec![]).with_details(details),
+            ]);
+        }
+    };
+    let object_ids: HashSet<String> = match serde_json::from_str(&object_ids_json) {
+        Ok(value) => value,
+        Err(err) => {
+            let mut details = std::collections::HashMap::new();
+            details.insert("message".to_string(), serde_json::Value::String(err.to_string()));
+            return violations_response(vec![
+                Violation::error("json.parse_error", "Invalid object ids JSON", vec![]).with_details(details),
+            ]);
+        }
+    };
+
+    violations_response(validate_layout_scoped(&kitchen_state, &ValidationScope::Objects(object_ids)))
+}
+
+/// Structural checks on the state followed by the full constraint set.
+pub fn validate_layout(kitchen_state: &KitchenState) -> Vec<Violation> {
+    validate_layout_scoped(kitchen_state, &ValidationScope::All)
+}
+
+pub fn validate_layout_scoped(kitchen_state: &KitchenState, scope: &ValidationScope) -> Vec<Violation> {
+    validate_layout_with(kitchen_state, scope, &RulesetCatalog::builtin(), &PluginRegistry::with_builtin())
+}
+
+/// Like `validate_layout_scoped`, with the rulesets to resolve `ruleset_version` against and the
+/// rules to run.
+pub fn validate_layout_with(
+    kitchen_state: &KitchenState,
+    scope: &ValidationScope,
+    rulesets: &RulesetCatalog,
+    registry: &PluginRegistry,
+) -> Vec<Violation> {
+    let mut violations: Vec<Violation> = Vec::new();
     let size = &kitchen_state.room.size_mm;
-    if size.width <= 0 || size.depth <= 0 || size.height <= 0 {
-        violations.push(Violation::error(
-            "room.invalid_size",
-            "room size must be positive",
-            vec![],
-        ));
+    let outline = build_room_outline(&kitchen_state.room);
+
+    if let ValidationScope::All = scope {
+        if kitchen_state.schema_version.trim().is_empty() {
+            violations.push(Violation::error(
+                "schema.empty_version",
+                "schema_version must be set",
+                vec![],
+            ));
+        }
+
+        if size.width <= 0 || size.depth <= 0 || size.height <= 0 {
+            violations.push(Violation::error(
+                "room.invalid_size",
+                "room size must be positive",
+                vec![],
+            ));
+        }
+
+        if let Some(walls) = &kitchen_state.room.outline_mm {
+            let mut wall_ids = HashSet::new();
+            let unique = walls.iter().all(|wall| wall_ids.insert(wall.id.as_str()));
+            if !unique || !outline.polygon.is_simple() {
+                violations.push(Violation::error(
+                    "room.invalid_outline",
+                    "room outline must be a simple polygon with unique wall ids",
+                    vec![],
+                ));
+            }
+        }
     }
 
+    let footprints = build_footprints(&kitchen_state.layout.objects);
     let mut ids = HashSet::new();
-    for obj in &kitchen_state.layout.objects {
-        if !ids.insert(obj.id.clone()) {
+    for (obj, footprint) in kitchen_state.layout.objects.iter().zip(&footprints) {
+        let unique = ids.insert(obj.id.clone());
+        if !scope.includes(&obj.id) {
+            continue;
+        }
+
+        if !unique {
             violations.push(Violation::error(
                 "layout.duplicate_id",
                 "layout object ids must be unique",
@@ -57,6 +161,14 @@ pub fn validate_layout_json(kitchen_state_json: String) -> String {
             ));
         }
 
+        if obj.transform_mm.elevation() < 0 {
+            violations.push(Violation::error(
+                "layout.invalid_elevation",
+                "elevation_mm must not be negative",
+                vec![obj.id.clone()],
+            ));
+        }
+
         if obj.transform_mm.rotation_deg < 0 || obj.transform_mm.rotation_deg > 359 {
             violations.push(Violation::error(
                 "layout.invalid_rotation",
@@ -65,12 +177,7 @@ pub fn validate_layout_json(kitchen_state_json: String) -> String {
             ));
         }
 
-        let x = obj.transform_mm.position_mm.x;
-        let y = obj.transform_mm.position_mm.y;
-        let max_x = x + obj.dims_mm.width;
-        let max_y = y + obj.dims_mm.depth;
-
-        if x < 0 || y < 0 || max_x > size.width || max_y > size.depth {
+        if !outline.contains(&footprint.obb) {
             violations.push(Violation::error(
                 "layout.out_of_bounds",
                 "layout object must fit inside room bounds",
@@ -79,7 +186,7 @@ pub fn validate_layout_json(kitchen_state_json: String) -> String {
         }
     }
 
-    violations.extend(validate_constraints(&kitchen_state));
+    violations.extend(validate_constraints_with(kitchen_state, scope, rulesets, registry));
 
-    violations_response(violations)
+    violations
 }
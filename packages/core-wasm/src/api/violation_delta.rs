@@ -0,0 +1,115 @@
+use crate::api::validate_layout::validate_layout;
+use crate::model::kitchen_state::KitchenState;
+use crate::model::violation::{Severity, Violation, ViolationSummary};
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+fn violations_response(violations: Vec<Violation>) -> String {
+    serde_json::to_string(&json!({ "violations": violations })).unwrap_or_else(|_| "{\"violations\":[]}".to_string())
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ViolationDelta {
+    pub introduced: Vec<Violation>,
+    pub resolved: Vec<Violation>,
+    pub persisting: Vec<Violation>,
+}
+
+/// Violations are the same issue when code and object ids match, regardless of id order.
+fn violation_key(violation: &Violation) -> (String, Vec<String>) {
+    let mut ids = violation.object_ids.clone();
+    ids.sort();
+    (violation.code.clone(), ids)
+}
+
+/// Splits `from` into the violations matched one-to-one in `against` and the unmatched rest.
+fn partition(from: &[Violation], against: &[Violation]) -> (Vec<Violation>, Vec<Violation>) {
+    let mut remaining: HashMap<(String, Vec<String>), usize> = HashMap::new();
+    for violation in against {
+        *remaining.entry(violation_key(violation)).or_insert(0) += 1;
+    }
+    let mut matched = Vec::new();
+    let mut unmatched = Vec::new();
+    for violation in from {
+        match remaining.get_mut(&violation_key(violation)) {
+            Some(count) if *count > 0 => {
+                *count -= 1;
+                matched.push(violation.clone());
+            }
+            _ => unmatched.push(violation.clone()),
+        }
+    }
+    (matched, unmatched)
+}
+
+pub fn violation_delta(before: &[Violation], after: &[Violation]) -> ViolationDelta {
+    let (persisting, introduced) = partition(after, before);
+    let (_, resolved) = partition(before, after);
+    ViolationDelta {
+        introduced,
+        resolved,
+        persisting,
+    }
+}
+
+fn severity_name(severity: &Severity) -> &'static str {
+    match severity {
+        Severity::Info => "info",
+        Severity::Warning => "warning",
+        Severity::Error => "error",
+    }
+}
+
+/// Counts violations per code and severity, in order of first appearance.
+pub fn summarize_violations(violations: &[Violation]) -> Vec<ViolationSummary> {
+    let mut summary: Vec<ViolationSummary> = Vec::new();
+    let mut index: HashMap<(String, &'static str), usize> = HashMap::new();
+    for violation in violations {
+        let key = (violation.code.clone(), severity_name(&violation.severity));
+        match index.get(&key) {
+            Some(&i) => summary[i].count += 1,
+            None => {
+                index.insert(key, summary.len());
+                summary.push(ViolationSummary {
+                    code: violation.code.clone(),
+                    severity: violation.severity.clone(),
+                    count: 1,
+                });
+            }
+        }
+    }
+    summary
+}
+
+fn parse_state(kitchen_state_json: &str, message: &str) -> Result<KitchenState, Vec<Violation>> {
+    serde_json::from_str(kitchen_state_json).map_err(|err| {
+        let mut details = HashMap::new();
+        details.insert("message".to_string(), Value::String(err.to_string()));
+        vec![Violation::error("json.parse_error", message, vec![]).with_details(details)]
+    })
+}
+
+/// Validates both states and reports which violations `after` introduced, resolved or kept,
+/// plus the `violations_summary` of `after`.
+pub fn violation_delta_json(before_json: String, after_json: String) -> String {
+    let before = parse_state(&before_json, "Invalid before KitchenState JSON");
+    let after = parse_state(&after_json, "Invalid after KitchenState JSON");
+    let (before, after) = match (before, after) {
+        (Ok(before), Ok(after)) => (before, after),
+        (before, after) => {
+            return violations_response(before.err().into_iter().chain(after.err()).flatten().collect());
+        }
+    };
+
+    let after_violations = validate_layout(&after);
+    let delta = violation_delta(&validate_layout(&before), &after_violations);
+
+    serde_json::to_string(&json!({
+        "introduced": delta.introduced,
+        "resolved": delta.resolved,
+        "persisting": delta.persisting,
+        "violations_summary": summarize_violations(&after_violations),
+    }))
+    .unwrap_or_else(|_| "{}".to_string())
+}
@@ -0,0 +1,104 @@
+use crate::constraints::clearances::zone_polygon;
+use crate::constraints::{candidate_pairs, has_tag, Constraint, ConstraintCategory, ConstraintContext, Footprint};
+use crate::geometry::obb::Obb;
+use crate::model::layout::LayoutObjectKind;
+use crate::model::violation::{Severity, Violation};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Space needed in front of objects with these tags; the largest match wins.
+const ACCESS_DEPTHS_MM: &[(&[&str], i32)] = &[
+    (&["dishwasher"], 600),
+    (&["oven"], 600),
+    (&["fridge", "refrigerator"], 600),
+    (&["drawers", "drawer"], 550),
+];
+/// Space in front of any other module or appliance, enough for a cabinet door.
+const DEFAULT_ACCESS_DEPTH_MM: i32 = 400;
+
+/// Depth of the access zone in front of `fp`; decor needs none.
+pub fn access_depth_mm(fp: &Footprint) -> Option<i32> {
+    if matches!(fp.kind, LayoutObjectKind::Decor) {
+        return None;
+    }
+    let depth = ACCESS_DEPTHS_MM
+        .iter()
+        .filter(|(tags, _)| has_tag(&fp.tags, tags))
+        .map(|(_, depth)| *depth)
+        .max()
+        .unwrap_or(DEFAULT_ACCESS_DEPTH_MM);
+    Some(depth)
+}
+
+/// The zone in front of each object (drawer pull-out, dishwasher door drop, oven door) must stay
+/// free of other objects at the same height, walls and restricted zones. Each violation names
+/// the blocked object first and carries `details.access_depth_mm` and `details.blocked_by`.
+pub struct FrontAccess;
+
+impl FrontAccess {
+    fn blocked(&self, fp: &Footprint, depth: i32, blocked_by: &str, mut object_ids: Vec<String>) -> Violation {
+        object_ids.insert(0, fp.id.clone());
+        let mut details = HashMap::new();
+        details.insert("access_depth_mm".to_string(), Value::from(depth));
+        details.insert("blocked_by".to_string(), Value::String(blocked_by.to_string()));
+        self.violation("layout.front_access_blocked", "front access zone is blocked", object_ids)
+            .with_details(details)
+    }
+}
+
+impl Constraint for FrontAccess {
+    fn id(&self) -> &'static str {
+        "front_access"
+    }
+
+    fn category(&self) -> ConstraintCategory {
+        ConstraintCategory::Clearance
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn evaluate(&self, ctx: &ConstraintContext, violations: &mut Vec<Violation>) {
+        let footprints = ctx.footprints;
+        let zones: Vec<Option<(i32, Obb)>> = footprints
+            .iter()
+            .map(|fp| access_depth_mm(fp).map(|depth| (depth, fp.front_zone(depth as f64))))
+            .collect();
+        let max_depth = zones.iter().flatten().map(|(depth, _)| *depth).max().unwrap_or(0);
+
+        for (i, j) in candidate_pairs(footprints, ctx.scope, max_depth) {
+            for (owner, other) in [(i, j), (j, i)] {
+                let Some((depth, zone)) = &zones[owner] else {
+                    continue;
+                };
+                let (fp, intruder) = (&footprints[owner], &footprints[other]);
+                if fp.overlaps_vertically(intruder) && zone.intersects(&intruder.obb) {
+                    violations.push(self.blocked(fp, *depth, &intruder.id, vec![intruder.id.clone()]));
+                }
+            }
+        }
+
+        for (fp, zone) in footprints.iter().zip(&zones) {
+            let Some((depth, zone)) = zone else {
+                continue;
+            };
+            if !ctx.scope.includes(&fp.id) {
+                continue;
+            }
+
+            // An object already outside the room is reported as such; its zone adds nothing.
+            if ctx.outline.contains(&fp.obb) && !ctx.outline.contains(zone) {
+                if let Some(wall) = ctx.outline.blocking_wall(zone) {
+                    violations.push(self.blocked(fp, *depth, &wall.id, vec![]));
+                }
+            }
+
+            for restricted in &ctx.state.room.restricted_zones {
+                if zone_polygon(restricted).is_some_and(|polygon| polygon.intersects_obb(zone)) {
+                    violations.push(self.blocked(fp, *depth, &restricted.id, vec![]));
+                }
+            }
+        }
+    }
+}
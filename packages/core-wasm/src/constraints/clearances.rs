@@ -1,97 +1,286 @@
-use crate::constraints::Footprint;
+use crate::constraints::placement::FILLER_MAX_GAP_MM;
+use crate::constraints::{candidate_pairs, Constraint, ConstraintCategory, ConstraintContext, ValidationScope};
 use crate::geometry::aabb::Aabb;
-use crate::model::kitchen_state::KitchenState;
-use crate::model::violation::Violation;
-
-const MIN_WALL_CLEARANCE_MM: i32 = 0;
-const MIN_PASSAGE_MM: i32 = 900;
-
-pub fn check_clearances(state: &KitchenState, footprints: &[Footprint], violations: &mut Vec<Violation>) {
-    let room = &state.room.size_mm;
-    for fp in footprints {
-        if fp.width > room.width || fp.depth > room.depth || fp.height > room.height {
-            violations.push(Violation::error(
-                "layout.object_too_large",
-                "layout object exceeds room size",
-                vec![fp.id.clone()],
-            ));
-        }
+use crate::geometry::obb::Vec2;
+use crate::geometry::polygon::Polygon;
+use crate::geometry::sweep::sweep_pairs_between;
+use crate::model::room::RestrictedZone;
+use crate::model::violation::{Severity, Violation};
+use serde_json::Value;
+use std::collections::HashMap;
 
-        if fp.aabb.min_x < MIN_WALL_CLEARANCE_MM
-            || fp.aabb.min_y < MIN_WALL_CLEARANCE_MM
-            || room.width - fp.aabb.max_x < MIN_WALL_CLEARANCE_MM
-            || room.depth - fp.aabb.max_y < MIN_WALL_CLEARANCE_MM
-        {
-            violations.push(Violation::error(
-                "layout.wall_clearance",
-                "layout object too close to wall",
-                vec![fp.id.clone()],
-            ));
-        }
+/// Overlap below this (1 mm²) is treated as objects merely touching a zone.
+const RESTRICTED_ZONE_TOLERANCE_MM2: f64 = 1.0;
+
+/// Objects larger than the room in plan or taller than the ceiling.
+pub struct ObjectSize;
+
+impl Constraint for ObjectSize {
+    fn id(&self) -> &'static str {
+        "object_size"
     }
 
-    for zone in &state.room.restricted_zones {
-        let zone_aabb = if let Some(aabb) = &zone.aabb_mm {
-            Some(Aabb::from_min_max(
-                aabb.min_mm.x,
-                aabb.min_mm.y,
-                aabb.max_mm.x,
-                aabb.max_mm.y,
-            ))
-        } else if let Some(poly) = &zone.polygon_mm {
-            let (mut min_x, mut min_y, mut max_x, mut max_y) = (i32::MAX, i32::MAX, i32::MIN, i32::MIN);
-            for p in poly {
-                min_x = min_x.min(p.x);
-                min_y = min_y.min(p.y);
-                max_x = max_x.max(p.x);
-                max_y = max_y.max(p.y);
-            }
-            if min_x <= max_x && min_y <= max_y {
-                Some(Aabb::from_min_max(min_x, min_y, max_x, max_y))
-            } else {
-                None
+    fn category(&self) -> ConstraintCategory {
+        ConstraintCategory::Clearance
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn evaluate(&self, ctx: &ConstraintContext, violations: &mut Vec<Violation>) {
+        let bounds = ctx.outline.bounds();
+        let (room_width, room_depth) = (bounds.max_x - bounds.min_x, bounds.max_y - bounds.min_y);
+        for fp in ctx.footprints.iter().filter(|fp| ctx.scope.includes(&fp.id)) {
+            if fp.width > room_width || fp.depth > room_depth || fp.top() > ctx.state.room.size_mm.height {
+                violations.push(self.violation(
+                    "layout.object_too_large",
+                    "layout object exceeds room size",
+                    vec![fp.id.clone()],
+                ));
             }
-        } else {
-            None
-        };
+        }
+    }
+}
 
-        let Some(zone_aabb) = zone_aabb else {
-            continue;
-        };
-        for fp in footprints {
-            if fp.aabb.intersects(&zone_aabb) {
-                violations.push(Violation::error(
-                    "layout.restricted_zone",
-                    "layout object overlaps restricted zone",
+/// Objects outside the room or closer to a wall than `min_wall_clearance_mm`.
+pub struct WallClearance;
+
+impl Constraint for WallClearance {
+    fn id(&self) -> &'static str {
+        "wall_clearance"
+    }
+
+    fn category(&self) -> ConstraintCategory {
+        ConstraintCategory::Clearance
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn evaluate(&self, ctx: &ConstraintContext, violations: &mut Vec<Violation>) {
+        let min_clearance = ctx.rules.min_wall_clearance_mm as f64;
+        for fp in ctx.footprints.iter().filter(|fp| ctx.scope.includes(&fp.id)) {
+            let too_close = ctx
+                .outline
+                .walls
+                .iter()
+                .any(|wall| fp.obb.segment_distance(wall.start, wall.end) < min_clearance);
+            if too_close || !ctx.outline.contains(&fp.obb) {
+                violations.push(self.violation(
+                    "layout.wall_clearance",
+                    "layout object too close to wall",
                     vec![fp.id.clone()],
                 ));
             }
         }
     }
+}
 
-    for (i, a) in footprints.iter().enumerate() {
-        for b in footprints.iter().skip(i + 1) {
-            if a.aabb.overlaps_y(&b.aabb) {
-                let gap = a.aabb.gap_x(&b.aabb);
-                if gap > 0 && gap < MIN_PASSAGE_MM {
-                    violations.push(Violation::error(
-                        "layout.min_passage",
-                        "minimum passage width violated",
-                        vec![a.id.clone(), b.id.clone()],
-                    ));
+/// Objects overlapping a restricted zone of the room.
+pub struct RestrictedZones;
+
+impl Constraint for RestrictedZones {
+    fn id(&self) -> &'static str {
+        "restricted_zone"
+    }
+
+    fn category(&self) -> ConstraintCategory {
+        ConstraintCategory::Clearance
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn evaluate(&self, ctx: &ConstraintContext, violations: &mut Vec<Violation>) {
+        for zone in &ctx.state.room.restricted_zones {
+            let Some(polygon) = zone_polygon(zone) else {
+                continue;
+            };
+
+            for fp in ctx.footprints.iter().filter(|fp| ctx.scope.includes(&fp.id)) {
+                let overlap = polygon.overlap_area_mm2(&fp.obb);
+                if overlap > RESTRICTED_ZONE_TOLERANCE_MM2 {
+                    let mut details = HashMap::new();
+                    details.insert("zone_id".to_string(), Value::String(zone.id.clone()));
+                    details.insert("overlap_area_mm2".to_string(), Value::from(overlap.round() as i64));
+                    violations.push(
+                        self.violation(
+                            "layout.restricted_zone",
+                            "layout object overlaps restricted zone",
+                            vec![fp.id.clone()],
+                        )
+                        .with_details(details),
+                    );
                 }
             }
+        }
+    }
+}
 
-            if a.aabb.overlaps_x(&b.aabb) {
-                let gap = a.aabb.gap_y(&b.aabb);
-                if gap > 0 && gap < MIN_PASSAGE_MM {
-                    violations.push(Violation::error(
-                        "layout.min_passage",
-                        "minimum passage width violated",
-                        vec![a.id.clone(), b.id.clone()],
-                    ));
-                }
+/// Outline of a restricted zone, from `aabb_mm` or else `polygon_mm`.
+pub(crate) fn zone_polygon(zone: &RestrictedZone) -> Option<Polygon> {
+    if let Some(aabb) = &zone.aabb_mm {
+        Some(Polygon::from_aabb(&Aabb::from_min_max(
+            aabb.min_mm.x,
+            aabb.min_mm.y,
+            aabb.max_mm.x,
+            aabb.max_mm.y,
+        )))
+    } else {
+        let poly = zone.polygon_mm.as_ref()?;
+        Some(Polygon::new(poly.iter().map(|p| Vec2::new(p.x as f64, p.y as f64)).collect()))
+    }
+}
+
+/// Walkways narrower than `min_passage_mm`, between two objects or between an object and a
+/// wall. `details.width_mm` carries the measured clear width and `details.wall_id` the wall.
+pub struct MinPassage;
+
+impl Constraint for MinPassage {
+    fn id(&self) -> &'static str {
+        "min_passage"
+    }
+
+    fn category(&self) -> ConstraintCategory {
+        ConstraintCategory::Clearance
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn evaluate(&self, ctx: &ConstraintContext, violations: &mut Vec<Violation>) {
+        let min_passage = ctx.rules.min_passage_mm;
+        for passage in passages(ctx, min_passage) {
+            if passage.width_mm >= min_passage as f64 {
+                continue;
+            }
+            let mut details = HashMap::new();
+            details.insert("width_mm".to_string(), Value::from(passage.width_mm.round() as i64));
+            details.insert("min_passage_mm".to_string(), Value::from(min_passage));
+            if let Some(wall_id) = passage.wall_id {
+                details.insert("wall_id".to_string(), Value::String(wall_id));
             }
+            violations.push(
+                self.violation("layout.min_passage", "minimum passage width violated", passage.object_ids)
+                    .with_details(details),
+            );
+        }
+    }
+}
+
+/// Clear floor-level gap between two objects, or between an object and a wall.
+pub(crate) struct Passage {
+    pub object_ids: Vec<String>,
+    pub wall_id: Option<String>,
+    pub width_mm: f64,
+}
+
+/// Every walkway narrower than `max_width_mm` that involves an in-scope object. Widths are true
+/// clear distances, so diagonally offset and rotated objects are measured corner to corner.
+/// Gaps a filler panel closes, and the service void behind a unit, are not walkways.
+pub(crate) fn passages(ctx: &ConstraintContext, max_width_mm: i32) -> Vec<Passage> {
+    let footprints = ctx.footprints;
+    let is_walkway = |width: f64| width > FILLER_MAX_GAP_MM && width < max_width_mm as f64;
+    let mut gaps = Vec::new();
+
+    // Anything that can stand in a gap is within its width (plus a filler) of the gap's owner.
+    let reach = max_width_mm + FILLER_MAX_GAP_MM as i32 + 1;
+    let mut nearby: Vec<Vec<usize>> = vec![Vec::new(); footprints.len()];
+    for (i, j) in candidate_pairs(footprints, &ValidationScope::All, reach) {
+        nearby[i].push(j);
+        nearby[j].push(i);
+        let (a, b) = (&footprints[i], &footprints[j]);
+        if !ctx.scope.includes(&a.id) && !ctx.scope.includes(&b.id) {
+            continue;
+        }
+        if !a.overlaps_vertically(b) {
+            // A wall unit over a worktop narrows nothing at floor level.
+            continue;
+        }
+        if a.obb.intersects(&b.obb) {
+            continue;
+        }
+        let (p, q) = a.obb.closest_points(&b.obb);
+        let width = (q - p).length();
+        if is_walkway(width) {
+            gaps.push(Gap {
+                passage: Passage {
+                    object_ids: vec![a.id.clone(), b.id.clone()],
+                    wall_id: None,
+                    width_mm: width,
+                },
+                owner: i,
+                other: Some(j),
+                line: (p, q),
+            });
         }
     }
+
+    // Only floor-standing objects near a wall narrow the walkway along it.
+    let boxes: Vec<Aabb> = footprints.iter().map(|fp| fp.aabb.clone()).collect();
+    let walls = &ctx.outline.walls;
+    let wall_boxes: Vec<Aabb> = walls.iter().map(|wall| segment_box(wall.start, wall.end)).collect();
+    for (i, w) in sweep_pairs_between(&boxes, &wall_boxes, max_width_mm) {
+        let (fp, wall) = (&footprints[i], &walls[w]);
+        if fp.elevation > 0 || !ctx.scope.includes(&fp.id) || fp.obb.intersects_segment(wall.start, wall.end) {
+            continue;
+        }
+        let (p, q) = fp.obb.closest_points_to_segment(wall.start, wall.end);
+        let width = (q - p).length();
+        if is_walkway(width) {
+            gaps.push(Gap {
+                passage: Passage {
+                    object_ids: vec![fp.id.clone()],
+                    wall_id: Some(wall.id.clone()),
+                    width_mm: width,
+                },
+                owner: i,
+                other: None,
+                line: (p, q),
+            });
+        }
+    }
+
+    // A gap another object at the same height stands across, or whose middle runs through a
+    // slit no wider than a filler, is not a walkway of its own.
+    let slit = FILLER_MAX_GAP_MM as i32;
+    gaps.into_iter()
+        .filter(|gap| {
+            let (p, q) = gap.line;
+            let (owner, line) = (&footprints[gap.owner], segment_box(p, q));
+            !nearby[gap.owner].iter().any(|&k| {
+                let blocker = &footprints[k];
+                Some(k) != gap.other
+                    && blocker.aabb.gap_x(&line) <= slit
+                    && blocker.aabb.gap_y(&line) <= slit
+                    && owner.overlaps_vertically(blocker)
+                    && (blocker.obb.intersects_segment(p, q)
+                        || blocker.obb.point_distance((p + q).scale(0.5)) <= FILLER_MAX_GAP_MM)
+            })
+        })
+        .map(|gap| gap.passage)
+        .collect()
+}
+
+/// A walkway candidate: `owner` (and `other`, unless it is a wall gap) with the clear line `p`-`q`
+/// between them.
+struct Gap {
+    passage: Passage,
+    owner: usize,
+    other: Option<usize>,
+    line: (Vec2, Vec2),
+}
+
+/// Integer box around the segment `a`-`b`.
+fn segment_box(a: Vec2, b: Vec2) -> Aabb {
+    Aabb::from_min_max(
+        a.x.min(b.x).floor() as i32,
+        a.y.min(b.y).floor() as i32,
+        a.x.max(b.x).ceil() as i32,
+        a.y.max(b.y).ceil() as i32,
+    )
 }
@@ -1,11 +1,27 @@
-use crate::constraints::Footprint;
-use crate::model::violation::Violation;
+use crate::constraints::{candidate_pairs, Constraint, ConstraintCategory, ConstraintContext};
+use crate::model::violation::{Severity, Violation};
 
-pub fn check_collisions(footprints: &[Footprint], violations: &mut Vec<Violation>) {
-    for (i, a) in footprints.iter().enumerate() {
-        for b in footprints.iter().skip(i + 1) {
-            if a.aabb.intersects(&b.aabb) {
-                violations.push(Violation::error(
+pub struct Collisions;
+
+impl Constraint for Collisions {
+    fn id(&self) -> &'static str {
+        "collision"
+    }
+
+    fn category(&self) -> ConstraintCategory {
+        ConstraintCategory::Collision
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn evaluate(&self, ctx: &ConstraintContext, violations: &mut Vec<Violation>) {
+        let footprints = ctx.footprints;
+        for (i, j) in candidate_pairs(footprints, ctx.scope, 0) {
+            let (a, b) = (&footprints[i], &footprints[j]);
+            if a.intersects(b) {
+                violations.push(self.violation(
                     "layout.collision",
                     "layout objects collide",
                     vec![a.id.clone(), b.id.clone()],
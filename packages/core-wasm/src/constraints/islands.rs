@@ -0,0 +1,155 @@
+use crate::constraints::clearances::passages;
+use crate::constraints::outline::RoomOutline;
+use crate::constraints::placement::is_fitted;
+use crate::constraints::{
+    candidate_pairs, has_tag, Constraint, ConstraintCategory, ConstraintContext, Face, Footprint, ValidationScope,
+};
+use crate::geometry::obb::Obb;
+use crate::model::violation::{Severity, Violation};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+
+/// A face with a unit or wall this close is joined to it rather than open.
+const ATTACHED_GAP_MM: f64 = 5.0;
+/// Worktop overhang kept free for knees on the seating side.
+const SEATING_OVERHANG_MM: i32 = 300;
+
+/// Whether anything fitted among `nearby` or a wall sits against `face`.
+fn is_attached(fp: &Footprint, face: Face, nearby: &[&Footprint], outline: &RoomOutline) -> bool {
+    let strip = fp.face_zone(face, ATTACHED_GAP_MM);
+    !outline.contains(&strip)
+        || nearby
+            .iter()
+            .any(|other| is_fitted(other) && fp.overlaps_vertically(other) && strip.intersects(&other.obb))
+}
+
+/// Every open side of an island or peninsula needs `min_island_walkway_mm` of clear floor to the
+/// next wall or unit. A unit tagged `seating` is used from its back, which additionally needs a
+/// free overhang of 300 mm in front of that walkway. Faces joined to another unit or a wall are
+/// not open, so an island built from several units is checked on its outer faces only. Gaps
+/// already narrower than `min_passage_mm` are left to `layout.min_passage`.
+pub struct IslandClearance;
+
+impl IslandClearance {
+    fn walkway(&self, fp: &Footprint, face: Face, clear_mm: f64, min_mm: i32, blocked_by: &str, mut ids: Vec<String>) -> Violation {
+        ids.insert(0, fp.id.clone());
+        let mut details = HashMap::new();
+        details.insert("face".to_string(), Value::String(face.as_str().to_string()));
+        details.insert("walkway_mm".to_string(), Value::from(clear_mm.max(0.0).round() as i64));
+        details.insert("min_walkway_mm".to_string(), Value::from(min_mm));
+        details.insert("blocked_by".to_string(), Value::String(blocked_by.to_string()));
+        self.violation("layout.island_walkway", "walkway around island is too narrow", ids)
+            .with_details(details)
+    }
+
+    /// `(object, object or wall)` gaps that `layout.min_passage` reports, unless the project
+    /// switched that rule off.
+    fn min_passage_gaps(&self, ctx: &ConstraintContext) -> HashSet<(String, String)> {
+        let disabled = ctx.state.project.disabled_constraints.as_deref().unwrap_or_default();
+        if disabled.iter().any(|id| id == "min_passage") {
+            return HashSet::new();
+        }
+        let mut gaps = HashSet::new();
+        for passage in passages(ctx, ctx.rules.min_passage_mm) {
+            let ids = passage.object_ids;
+            match passage.wall_id {
+                Some(wall_id) => {
+                    gaps.insert((ids[0].clone(), wall_id));
+                }
+                None => {
+                    gaps.insert((ids[0].clone(), ids[1].clone()));
+                    gaps.insert((ids[1].clone(), ids[0].clone()));
+                }
+            }
+        }
+        gaps
+    }
+}
+
+impl Constraint for IslandClearance {
+    fn id(&self) -> &'static str {
+        "island_clearance"
+    }
+
+    fn category(&self) -> ConstraintCategory {
+        ConstraintCategory::Clearance
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn evaluate(&self, ctx: &ConstraintContext, violations: &mut Vec<Violation>) {
+        let footprints = ctx.footprints;
+        let min_walkway = ctx.rules.min_island_walkway_mm.max(0);
+        let is_island = |fp: &Footprint| fp.is_island() || fp.is_peninsula();
+        if !footprints.iter().any(is_island) {
+            return;
+        }
+
+        // Everything that can reach into a walkway zone, in or out of scope, since units out of
+        // scope still close off the faces they are joined to.
+        let mut nearby: HashMap<usize, Vec<&Footprint>> = HashMap::new();
+        for (i, j) in candidate_pairs(footprints, &ValidationScope::All, SEATING_OVERHANG_MM + min_walkway + 1) {
+            if is_island(&footprints[i]) {
+                nearby.entry(i).or_default().push(&footprints[j]);
+            }
+            if is_island(&footprints[j]) {
+                nearby.entry(j).or_default().push(&footprints[i]);
+            }
+        }
+        let min_passage_reported = self.min_passage_gaps(ctx);
+
+        for (i, fp) in footprints.iter().enumerate().filter(|(_, fp)| is_island(fp)) {
+            let nearby = nearby.get(&i).map(Vec::as_slice).unwrap_or_default();
+            for face in Face::ALL {
+                if is_attached(fp, face, nearby, ctx.outline) {
+                    continue;
+                }
+                let seating = face == Face::Back && has_tag(&fp.tags, &["seating"]);
+                let overhang = if seating { SEATING_OVERHANG_MM } else { 0 };
+                let overhang_zone = (overhang > 0).then(|| fp.face_zone(face, overhang as f64));
+                let zone: Obb = fp.face_zone(face, (overhang + min_walkway) as f64);
+
+                let mut nearest_object = f64::MAX;
+                for other in nearby {
+                    let relevant = is_fitted(other)
+                        && fp.overlaps_vertically(other)
+                        && (ctx.scope.includes(&fp.id) || ctx.scope.includes(&other.id));
+                    if !relevant || !zone.intersects(&other.obb) {
+                        continue;
+                    }
+                    if overhang_zone.as_ref().is_some_and(|knees| knees.intersects(&other.obb)) {
+                        let mut details = HashMap::new();
+                        details.insert("overhang_mm".to_string(), Value::from(overhang));
+                        violations.push(
+                            self.violation(
+                                "layout.island_seating_blocked",
+                                "island seating overhang is blocked",
+                                vec![fp.id.clone(), other.id.clone()],
+                            )
+                            .with_details(details),
+                        );
+                        continue;
+                    }
+                    let clear = fp.obb.distance(&other.obb) - overhang as f64;
+                    nearest_object = nearest_object.min(clear);
+                    if !min_passage_reported.contains(&(fp.id.clone(), other.id.clone())) {
+                        violations.push(self.walkway(fp, face, clear, min_walkway, &other.id, vec![other.id.clone()]));
+                    }
+                }
+
+                if ctx.scope.includes(&fp.id) && ctx.outline.contains(&fp.obb) && !ctx.outline.contains(&zone) {
+                    if let Some(wall) = ctx.outline.blocking_wall(&zone) {
+                        // A wall behind a unit that already narrows the walkway adds nothing.
+                        let clear = fp.obb.segment_distance(wall.start, wall.end) - overhang as f64;
+                        let reported = min_passage_reported.contains(&(fp.id.clone(), wall.id.clone()));
+                        if clear < nearest_object && !reported {
+                            violations.push(self.walkway(fp, face, clear, min_walkway, &wall.id, vec![]));
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,39 +1,137 @@
+pub mod access;
 pub mod clearances;
 pub mod collisions;
+pub mod islands;
 pub mod openings;
+pub mod outline;
+pub mod placement;
 pub mod plumbing_power;
+pub mod ruleset;
 
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+use crate::constraints::outline::{build_room_outline, RoomOutline};
+use crate::constraints::ruleset::RulesetCatalog;
 use crate::geometry::aabb::Aabb;
+use crate::geometry::obb::{Obb, Vec2};
+use crate::geometry::sweep::sweep_pairs;
 use crate::model::kitchen_state::KitchenState;
-use crate::model::layout::LayoutObject;
+use crate::model::layout::{LayoutObject, LayoutObjectKind};
 use crate::model::room::Point2Mm;
-use crate::model::violation::Violation;
+use crate::model::ruleset::Ruleset;
+use crate::model::violation::{Severity, Violation};
+use crate::plugins::registry::PluginRegistry;
 
+/// Oriented plan-view rectangle of a layout object plus its `elevation..elevation + height`
+/// span. The front face looks along `obb.axis_v()`.
 #[derive(Debug, Clone)]
 pub struct Footprint {
     pub id: String,
+    pub kind: LayoutObjectKind,
+    pub obb: Obb,
     pub aabb: Aabb,
     pub width: i32,
     pub depth: i32,
     pub height: i32,
+    pub elevation: i32,
     pub anchor: Point2Mm,
     pub tags: Option<Vec<String>>,
 }
 
+impl Footprint {
+    pub fn top(&self) -> i32 {
+        self.elevation + self.height
+    }
+
+    /// Whether the vertical extents overlap; a wall unit resting on a tall unit's top does not.
+    pub fn overlaps_vertically(&self, other: &Footprint) -> bool {
+        self.elevation < other.top() && other.elevation < self.top()
+    }
+
+    /// Whether the 3D boxes share interior.
+    pub fn intersects(&self, other: &Footprint) -> bool {
+        self.overlaps_vertically(other) && self.obb.intersects(&other.obb)
+    }
+
+    /// Rectangle of `depth_mm` directly in front of the object, as wide as the object.
+    pub fn front_zone(&self, depth_mm: f64) -> Obb {
+        self.face_zone(Face::Front, depth_mm)
+    }
+
+    /// Rectangle of `depth_mm` directly outside `face`, as long as the face.
+    pub fn face_zone(&self, face: Face, depth_mm: f64) -> Obb {
+        let (u, v) = (self.obb.axis_u, self.obb.axis_v());
+        let (normal, along, offset, length) = match face {
+            Face::Front => (v, u, self.obb.half_depth, self.obb.half_width),
+            Face::Back => (v.scale(-1.0), u, self.obb.half_depth, self.obb.half_width),
+            Face::Right => (u, v, self.obb.half_width, self.obb.half_depth),
+            Face::Left => (u.scale(-1.0), v, self.obb.half_width, self.obb.half_depth),
+        };
+        let center = self.obb.center + normal.scale(offset + depth_mm / 2.0);
+        Obb::from_axis(center, length * 2.0, depth_mm, along)
+    }
+
+    /// An island by kind or by the `island` tag.
+    pub fn is_island(&self) -> bool {
+        matches!(self.kind, LayoutObjectKind::Island) || has_tag(&self.tags, &["island"])
+    }
+
+    /// A peninsula by kind or by the `peninsula` tag.
+    pub fn is_peninsula(&self) -> bool {
+        matches!(self.kind, LayoutObjectKind::Peninsula) || has_tag(&self.tags, &["peninsula"])
+    }
+}
+
+/// A side of a footprint. `Left` and `Right` are as seen from the object looking out of its
+/// front.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Face {
+    Front,
+    Back,
+    Left,
+    Right,
+}
+
+impl Face {
+    pub const ALL: [Face; 4] = [Face::Front, Face::Back, Face::Left, Face::Right];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Face::Front => "front",
+            Face::Back => "back",
+            Face::Left => "left",
+            Face::Right => "right",
+        }
+    }
+}
+
 pub fn build_footprints(objects: &[LayoutObject]) -> Vec<Footprint> {
     objects
         .iter()
         .map(|obj| {
-            let (width, depth) = footprint_dims(obj);
             let x = obj.transform_mm.position_mm.x;
             let y = obj.transform_mm.position_mm.y;
-            let aabb = Aabb::from_min_max(x, y, x + width, y + depth);
+            // Rotate in place, then shift so the bounding box keeps its corner at `position_mm`.
+            let mut obb = Obb::new(
+                Vec2::new(0.0, 0.0),
+                obj.dims_mm.width as f64,
+                obj.dims_mm.depth as f64,
+                obj.transform_mm.rotation_deg,
+            );
+            let (min_x, _) = obb.project(Vec2::new(1.0, 0.0));
+            let (min_y, _) = obb.project(Vec2::new(0.0, 1.0));
+            obb.center = Vec2::new(x as f64 - min_x, y as f64 - min_y);
+            let aabb = obb.aabb();
             Footprint {
                 id: obj.id.clone(),
+                kind: obj.kind.clone(),
+                width: aabb.max_x - aabb.min_x,
+                depth: aabb.max_y - aabb.min_y,
+                obb,
                 aabb,
-                width,
-                depth,
                 height: obj.dims_mm.height,
+                elevation: obj.transform_mm.elevation(),
                 anchor: Point2Mm { x, y },
                 tags: obj.tags.clone(),
             }
@@ -41,23 +139,143 @@ pub fn build_footprints(objects: &[LayoutObject]) -> Vec<Footprint> {
         .collect()
 }
 
-pub fn validate_constraints(state: &KitchenState) -> Vec<Violation> {
-    let footprints = build_footprints(&state.layout.objects);
-    let mut violations = Vec::new();
+/// Case-insensitive match of any of `expected` against an object's tags.
+pub(crate) fn has_tag(tags: &Option<Vec<String>>, expected: &[&str]) -> bool {
+    let Some(list) = tags else {
+        return false;
+    };
+    list.iter()
+        .any(|tag| expected.iter().any(|e| tag.eq_ignore_ascii_case(e)))
+}
 
-    collisions::check_collisions(&footprints, &mut violations);
-    clearances::check_clearances(state, &footprints, &mut violations);
-    openings::check_openings(state, &footprints, &mut violations);
-    crate::constraints::plumbing_power::check_plumbing_power(state, &footprints, &mut violations);
+/// Which objects a validation pass has to look at. `All` is the full run; `Objects` is drag mode,
+/// where only violations involving at least one listed object are produced.
+#[derive(Debug, Clone)]
+pub enum ValidationScope {
+    All,
+    Objects(HashSet<String>),
+}
 
-    violations
+impl ValidationScope {
+    pub fn includes(&self, id: &str) -> bool {
+        match self {
+            ValidationScope::All => true,
+            ValidationScope::Objects(ids) => ids.contains(id),
+        }
+    }
+}
+
+/// Index pairs `(i, j)` with `i < j` whose footprints come within `margin_mm` of each other, in
+/// full-scan order, limited to pairs where at least one footprint is in scope. Pairwise rules
+/// go through this instead of an O(n²) loop.
+pub fn candidate_pairs(footprints: &[Footprint], scope: &ValidationScope, margin_mm: i32) -> Vec<(usize, usize)> {
+    let boxes: Vec<Aabb> = footprints.iter().map(|fp| fp.aabb.clone()).collect();
+    sweep_pairs(&boxes, margin_mm)
+        .into_iter()
+        .filter(|&(i, j)| scope.includes(&footprints[i].id) || scope.includes(&footprints[j].id))
+        .collect()
+}
+
+/// Broad grouping of constraints, for listing and filtering rules.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConstraintCategory {
+    Collision,
+    Clearance,
+    Opening,
+    Placement,
+    Utility,
 }
 
-fn footprint_dims(obj: &LayoutObject) -> (i32, i32) {
-    let rot = obj.transform_mm.rotation_deg.rem_euclid(360);
-    if rot % 180 == 0 {
-        (obj.dims_mm.width, obj.dims_mm.depth)
-    } else {
-        (obj.dims_mm.depth, obj.dims_mm.width)
+/// Everything a rule may look at during one validation pass.
+pub struct ConstraintContext<'a> {
+    pub state: &'a KitchenState,
+    pub footprints: &'a [Footprint],
+    pub outline: &'a RoomOutline,
+    pub scope: &'a ValidationScope,
+    pub rules: &'a Ruleset,
+}
+
+/// A validation rule. Built-in rules and native rule packs implement this and are registered
+/// with a `PluginRegistry`; projects can switch rules off by id through
+/// `project.disabled_constraints`.
+pub trait Constraint {
+    /// Stable id used to enable or disable the rule.
+    fn id(&self) -> &'static str;
+
+    fn category(&self) -> ConstraintCategory;
+
+    fn default_severity(&self) -> Severity;
+
+    /// Appends the rule's violations. Rules must respect `ctx.scope`: in drag mode only
+    /// violations involving an in-scope object may be produced.
+    fn evaluate(&self, ctx: &ConstraintContext, violations: &mut Vec<Violation>);
+
+    /// A violation at the rule's default severity.
+    fn violation(&self, code: &str, message: &str, object_ids: Vec<String>) -> Violation {
+        match self.default_severity() {
+            Severity::Error => Violation::error(code, message, object_ids),
+            Severity::Warning => Violation::warning(code, message, object_ids),
+            Severity::Info => Violation::info(code, message, object_ids),
+        }
     }
 }
+
+/// Registers the rules that ship with the core, in evaluation order.
+pub fn register_builtin(registry: &mut PluginRegistry) {
+    registry.register(Box::new(collisions::Collisions));
+    registry.register(Box::new(clearances::ObjectSize));
+    registry.register(Box::new(clearances::WallClearance));
+    registry.register(Box::new(clearances::RestrictedZones));
+    registry.register(Box::new(clearances::MinPassage));
+    registry.register(Box::new(access::FrontAccess));
+    registry.register(Box::new(placement::FloatingObject));
+    registry.register(Box::new(placement::FillerGap));
+    registry.register(Box::new(islands::IslandClearance));
+    registry.register(Box::new(openings::DoorClearance));
+    registry.register(Box::new(openings::WindowCoverage));
+    registry.register(Box::new(openings::HobUnderWindow));
+    registry.register(Box::new(plumbing_power::SinkNearWater));
+    registry.register(Box::new(plumbing_power::HobNearVent));
+    registry.register(Box::new(plumbing_power::GasHobNearGas));
+    registry.register(Box::new(plumbing_power::ApplianceNearPower));
+    registry.register(Box::new(plumbing_power::UtilitySeparation));
+}
+
+pub fn validate_constraints(state: &KitchenState) -> Vec<Violation> {
+    validate_constraints_scoped(state, &ValidationScope::All)
+}
+
+pub fn validate_constraints_scoped(state: &KitchenState, scope: &ValidationScope) -> Vec<Violation> {
+    validate_constraints_with(state, scope, &RulesetCatalog::builtin(), &PluginRegistry::with_builtin())
+}
+
+/// Runs the registered rules the project has not disabled, with the parameters of its
+/// `ruleset_version` from `rulesets`. An unknown version yields a single
+/// `ruleset.unknown_version` error instead of rule results.
+pub fn validate_constraints_with(
+    state: &KitchenState,
+    scope: &ValidationScope,
+    rulesets: &RulesetCatalog,
+    registry: &PluginRegistry,
+) -> Vec<Violation> {
+    let rules = match rulesets.resolve(state.project.ruleset_version.as_deref()) {
+        Ok(rules) => rules,
+        Err(violations) => return violations,
+    };
+    let footprints = build_footprints(&state.layout.objects);
+    let outline = build_room_outline(&state.room);
+    let ctx = ConstraintContext {
+        state,
+        footprints: &footprints,
+        outline: &outline,
+        scope,
+        rules,
+    };
+
+    let mut violations = Vec::new();
+    for constraint in registry.enabled_for(&state.project) {
+        constraint.evaluate(&ctx, &mut violations);
+    }
+    violations
+}
@@ -1,90 +1,208 @@
-use crate::constraints::Footprint;
-use crate::geometry::aabb::Aabb;
-use crate::model::kitchen_state::KitchenState;
-use crate::model::violation::Violation;
-
-const DEFAULT_DOOR_SWING_MM: i32 = 900;
-
-fn wall_axis_length(room: &crate::model::room::SizeMm, wall_id: &str) -> Option<i32> {
-    match wall_id {
-        "north" | "south" => Some(room.width),
-        "east" | "west" => Some(room.depth),
-        _ => None,
-    }
+use crate::constraints::outline::Wall;
+use crate::constraints::{has_tag, Constraint, ConstraintCategory, ConstraintContext};
+use crate::geometry::obb::{Obb, Vec2};
+use crate::geometry::sector::Sector;
+use crate::model::layout::LayoutObjectKind;
+use crate::model::room::{DoorOpens, DoorSwingDirection, Opening, OpeningKind};
+use crate::model::ruleset::Ruleset;
+use crate::model::violation::{Severity, Violation};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// How far into the room an object may stand and still be in front of a window.
+const WINDOW_ZONE_DEPTH_MM: i32 = 600;
+/// Floor-standing units at least this tall count as tall units rather than base units.
+const TALL_UNIT_MIN_HEIGHT_MM: i32 = 1500;
+
+/// Openings of `kind` together with the wall they sit in; openings on unknown walls are skipped.
+fn openings_of<'a>(ctx: &'a ConstraintContext, kind: OpeningKind) -> impl Iterator<Item = (&'a Opening, &'a Wall)> {
+    ctx.state
+        .room
+        .openings
+        .iter()
+        .filter(move |opening| opening.kind == kind)
+        .filter_map(move |opening| Some((opening, ctx.outline.wall(&opening.wall_id)?)))
+}
+
+/// Area a door needs kept clear inside the room.
+enum DoorZone {
+    /// Hinge side unknown: the full `width x default_door_swing_mm` rectangle.
+    Rect(Obb),
+    /// One quarter-circle sector per leaf.
+    Leaves(Vec<Sector>),
 }
 
-fn door_clearance_zone(
-    room: &crate::model::room::SizeMm,
-    wall_id: &str,
-    offset_mm: i32,
-    width_mm: i32,
-    swing_radius_mm: i32,
-) -> Option<Aabb> {
-    let depth = swing_radius_mm.max(0);
-    match wall_id {
-        "south" => Some(Aabb::from_min_max(
-            offset_mm,
-            0,
-            offset_mm + width_mm,
-            depth,
-        )),
-        "north" => Some(Aabb::from_min_max(
-            offset_mm,
-            room.depth - depth,
-            offset_mm + width_mm,
-            room.depth,
-        )),
-        "west" => Some(Aabb::from_min_max(
-            0,
-            offset_mm,
-            depth,
-            offset_mm + width_mm,
-        )),
-        "east" => Some(Aabb::from_min_max(
-            room.width - depth,
-            offset_mm,
-            room.width,
-            offset_mm + width_mm,
-        )),
-        _ => None,
+fn door_zone(opening: &Opening, wall: &Wall, rules: &Ruleset) -> DoorZone {
+    let Some(swing) = &opening.swing else {
+        return DoorZone::Rect(wall.zone(
+            opening.offset_mm as f64,
+            opening.width_mm as f64,
+            rules.default_door_swing_mm.max(0) as f64,
+        ));
+    };
+    if swing.opens == Some(DoorOpens::Outward) {
+        return DoorZone::Leaves(Vec::new());
     }
+
+    // Facing the door from inside, the viewer's left is the inward normal turned clockwise.
+    let left = Vec2::new(wall.inward.y, -wall.inward.x);
+    let along = wall.direction();
+    let jamb_a = wall.point_at(opening.offset_mm as f64);
+    let jamb_b = wall.point_at((opening.offset_mm + opening.width_mm) as f64);
+    let (left_jamb, right_jamb) = if along.dot(left) > 0.0 { (jamb_b, jamb_a) } else { (jamb_a, jamb_b) };
+
+    let radius = swing.radius_mm.max(0) as f64;
+    let leaf = |hinge: Vec2, towards: Vec2| Sector::new(hinge, radius, towards, wall.inward);
+    let right = left.scale(-1.0);
+    DoorZone::Leaves(match swing.direction {
+        DoorSwingDirection::Left => vec![leaf(left_jamb, right)],
+        DoorSwingDirection::Right => vec![leaf(right_jamb, left)],
+        DoorSwingDirection::Both => vec![leaf(left_jamb, right), leaf(right_jamb, left)],
+    })
 }
 
-pub fn check_openings(state: &KitchenState, footprints: &[Footprint], violations: &mut Vec<Violation>) {
-    let room = &state.room.size_mm;
-    for opening in &state.room.openings {
-        if opening.kind != crate::model::room::OpeningKind::Door {
-            continue;
+/// Objects standing in a door's clearance zone below the door head.
+pub struct DoorClearance;
+
+impl Constraint for DoorClearance {
+    fn id(&self) -> &'static str {
+        "door_clearance"
+    }
+
+    fn category(&self) -> ConstraintCategory {
+        ConstraintCategory::Opening
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn evaluate(&self, ctx: &ConstraintContext, violations: &mut Vec<Violation>) {
+        for (opening, wall) in openings_of(ctx, OpeningKind::Door) {
+            let zone = door_zone(opening, wall, ctx.rules);
+            for fp in ctx.footprints.iter().filter(|fp| ctx.scope.includes(&fp.id)) {
+                let blocked = match &zone {
+                    DoorZone::Rect(rect) => fp.obb.intersects(rect),
+                    DoorZone::Leaves(leaves) => leaves.iter().any(|leaf| leaf.intersects_obb(&fp.obb)),
+                };
+                if fp.elevation < opening.height_mm && blocked {
+                    violations.push(self.violation(
+                        "layout.door_clearance",
+                        "layout object blocks door clearance",
+                        vec![fp.id.clone()],
+                    ));
+                }
+            }
         }
+    }
+}
+
+/// Objects standing in front of a window are judged by how their vertical extent meets the
+/// window's `sill..sill + height` span: wall units and tall units must stay clear of it, and base
+/// units must keep their worktop at or below the sill.
+pub struct WindowCoverage;
+
+impl Constraint for WindowCoverage {
+    fn id(&self) -> &'static str {
+        "window_coverage"
+    }
 
-        if wall_axis_length(room, &opening.wall_id).is_none() {
-            continue;
+    fn category(&self) -> ConstraintCategory {
+        ConstraintCategory::Opening
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn evaluate(&self, ctx: &ConstraintContext, violations: &mut Vec<Violation>) {
+        for (opening, wall) in openings_of(ctx, OpeningKind::Window) {
+            let sill = opening.sill_height_mm.unwrap_or(0);
+            let head = sill + opening.height_mm;
+            let zone = window_zone(opening, wall);
+
+            for fp in ctx.footprints.iter().filter(|fp| ctx.scope.includes(&fp.id)) {
+                if !fp.obb.intersects(&zone) {
+                    continue;
+                }
+
+                let covers_window = fp.elevation < head && fp.top() > sill;
+                let violation = if fp.elevation > 0 {
+                    covers_window.then(|| {
+                        self.violation("layout.window_wall_unit", "wall unit covers window", vec![fp.id.clone()])
+                    })
+                } else if fp.top() >= TALL_UNIT_MIN_HEIGHT_MM {
+                    covers_window.then(|| {
+                        self.violation("layout.window_tall_unit", "tall unit covers window", vec![fp.id.clone()])
+                    })
+                } else if matches!(fp.kind, LayoutObjectKind::Module | LayoutObjectKind::Appliance)
+                    || fp.is_island()
+                    || fp.is_peninsula()
+                {
+                    (fp.top() > sill).then(|| {
+                        self.violation(
+                            "layout.window_worktop_above_sill",
+                            "worktop is higher than window sill",
+                            vec![fp.id.clone()],
+                        )
+                    })
+                } else {
+                    None
+                };
+                if let Some(violation) = violation {
+                    violations.push(violation.with_details(window_details(opening)));
+                }
+            }
         }
+    }
+}
+
+/// Hobs standing in front of a window below its sill.
+pub struct HobUnderWindow;
 
-        let swing_radius = opening
-            .swing
-            .as_ref()
-            .map(|s| s.radius_mm)
-            .unwrap_or(DEFAULT_DOOR_SWING_MM);
-
-        let Some(zone) = door_clearance_zone(
-            room,
-            &opening.wall_id,
-            opening.offset_mm,
-            opening.width_mm,
-            swing_radius,
-        ) else {
-            continue;
-        };
-
-        for fp in footprints {
-            if fp.aabb.intersects(&zone) {
-                violations.push(Violation::error(
-                    "layout.door_clearance",
-                    "layout object blocks door clearance",
-                    vec![fp.id.clone()],
-                ));
+impl Constraint for HobUnderWindow {
+    fn id(&self) -> &'static str {
+        "hob_under_window"
+    }
+
+    fn category(&self) -> ConstraintCategory {
+        ConstraintCategory::Opening
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn evaluate(&self, ctx: &ConstraintContext, violations: &mut Vec<Violation>) {
+        for (opening, wall) in openings_of(ctx, OpeningKind::Window) {
+            let sill = opening.sill_height_mm.unwrap_or(0);
+            let zone = window_zone(opening, wall);
+            for fp in ctx.footprints.iter().filter(|fp| ctx.scope.includes(&fp.id)) {
+                if fp.elevation < sill && has_tag(&fp.tags, &["hob", "cooktop"]) && fp.obb.intersects(&zone) {
+                    violations.push(
+                        self.violation(
+                            "layout.hob_under_window",
+                            "cooktop should not be placed under a window",
+                            vec![fp.id.clone()],
+                        )
+                        .with_details(window_details(opening)),
+                    );
+                }
             }
         }
     }
 }
+
+fn window_zone(opening: &Opening, wall: &Wall) -> Obb {
+    wall.zone(
+        opening.offset_mm as f64,
+        opening.width_mm as f64,
+        WINDOW_ZONE_DEPTH_MM as f64,
+    )
+}
+
+fn window_details(opening: &Opening) -> HashMap<String, Value> {
+    let mut details = HashMap::new();
+    details.insert("opening_id".to_string(), Value::String(opening.id.clone()));
+    details
+}
@@ -0,0 +1,139 @@
+use crate::geometry::aabb::Aabb;
+use crate::geometry::obb::{Obb, Vec2};
+use crate::geometry::polygon::Polygon;
+use crate::model::room::{Point2Mm, RoomModel};
+
+/// A room wall in plan view. Offsets along the wall are measured from `start`, and `inward` is
+/// the unit normal pointing into the room.
+#[derive(Debug, Clone)]
+pub struct Wall {
+    pub id: String,
+    pub start: Vec2,
+    pub end: Vec2,
+    pub inward: Vec2,
+}
+
+impl Wall {
+    pub fn length_mm(&self) -> f64 {
+        (self.end - self.start).length()
+    }
+
+    /// Unit vector from `start` to `end`; zero for a degenerate wall.
+    pub fn direction(&self) -> Vec2 {
+        let along = self.end - self.start;
+        match along.length() {
+            length if length > 0.0 => along.scale(1.0 / length),
+            _ => along,
+        }
+    }
+
+    pub fn point_at(&self, offset_mm: f64) -> Vec2 {
+        self.start + self.direction().scale(offset_mm)
+    }
+
+    /// Rectangle lying against the wall from `offset_mm` for `width_mm`, reaching `depth_mm`
+    /// into the room.
+    pub fn zone(&self, offset_mm: f64, width_mm: f64, depth_mm: f64) -> Obb {
+        let center = self.point_at(offset_mm + width_mm / 2.0) + self.inward.scale(depth_mm / 2.0);
+        Obb::from_axis(center, width_mm, depth_mm, self.direction())
+    }
+}
+
+/// Plan-view shape of the room: its floor polygon and the walls along it.
+#[derive(Debug, Clone)]
+pub struct RoomOutline {
+    pub polygon: Polygon,
+    pub walls: Vec<Wall>,
+}
+
+impl RoomOutline {
+    pub fn wall(&self, id: &str) -> Option<&Wall> {
+        self.walls.iter().find(|wall| wall.id == id)
+    }
+
+    pub fn wall_length_mm(&self, id: &str) -> Option<i32> {
+        self.wall(id).map(|wall| wall.length_mm().round() as i32)
+    }
+
+    /// Position `offset_mm` along wall `id`, rounded to whole millimetres.
+    pub fn wall_point(&self, id: &str, offset_mm: i32) -> Option<Point2Mm> {
+        let p = self.wall(id)?.point_at(offset_mm as f64);
+        Some(Point2Mm {
+            x: p.x.round() as i32,
+            y: p.y.round() as i32,
+        })
+    }
+
+    pub fn bounds(&self) -> Aabb {
+        self.polygon.bounds()
+    }
+
+    pub fn contains(&self, obb: &Obb) -> bool {
+        self.polygon.contains_obb(obb)
+    }
+
+    /// Wall that keeps `obb` from fitting in the room: one running through it, or else the
+    /// nearest one. Walls merely touching it rank after those it crosses.
+    pub fn blocking_wall(&self, obb: &Obb) -> Option<&Wall> {
+        self.walls
+            .iter()
+            .find(|wall| obb.intersects_segment(wall.start, wall.end))
+            .or_else(|| {
+                self.walls.iter().min_by(|a, b| {
+                    obb.segment_distance(a.start, a.end)
+                        .total_cmp(&obb.segment_distance(b.start, b.end))
+                })
+            })
+    }
+}
+
+/// Builds the room outline from `outline_mm`, or from the `size_mm` rectangle when no outline
+/// is given.
+pub fn build_room_outline(room: &RoomModel) -> RoomOutline {
+    match &room.outline_mm {
+        Some(walls) => {
+            let polygon = Polygon::new(
+                walls
+                    .iter()
+                    .map(|wall| Vec2::new(wall.start_mm.x as f64, wall.start_mm.y as f64))
+                    .collect(),
+            );
+            let counter_clockwise = polygon.signed_area_mm2() >= 0.0;
+            let walls = walls
+                .iter()
+                .zip(polygon.edges())
+                .map(|(wall, (start, end))| {
+                    let mut wall = Wall {
+                        id: wall.id.clone(),
+                        start,
+                        end,
+                        inward: Vec2::new(0.0, 0.0),
+                    };
+                    let dir = wall.direction();
+                    let left = Vec2::new(-dir.y, dir.x);
+                    wall.inward = if counter_clockwise { left } else { left.scale(-1.0) };
+                    wall
+                })
+                .collect();
+            RoomOutline { polygon, walls }
+        }
+        None => {
+            let (w, d) = (room.size_mm.width as f64, room.size_mm.depth as f64);
+            let wall = |id: &str, start: Vec2, end: Vec2, inward: Vec2| Wall {
+                id: id.to_string(),
+                start,
+                end,
+                inward,
+            };
+            RoomOutline {
+                polygon: Polygon::from_aabb(&Aabb::from_min_max(0, 0, room.size_mm.width, room.size_mm.depth)),
+                walls: vec![
+                    wall("south", Vec2::new(0.0, 0.0), Vec2::new(w, 0.0), Vec2::new(0.0, 1.0)),
+                    wall("north", Vec2::new(0.0, d), Vec2::new(w, d), Vec2::new(0.0, -1.0)),
+                    wall("west", Vec2::new(0.0, 0.0), Vec2::new(0.0, d), Vec2::new(1.0, 0.0)),
+                    wall("east", Vec2::new(w, 0.0), Vec2::new(w, d), Vec2::new(-1.0, 0.0)),
+                ],
+            }
+        }
+    }
+}
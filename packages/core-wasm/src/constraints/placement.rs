@@ -0,0 +1,200 @@
+use crate::constraints::outline::Wall;
+use crate::constraints::{
+    candidate_pairs, Constraint, ConstraintCategory, ConstraintContext, Footprint, ValidationScope,
+};
+use crate::geometry::obb::{point_segment_distance, Vec2};
+use crate::model::layout::LayoutObjectKind;
+use crate::model::violation::{Severity, Violation};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Gaps up to this wide are hidden by trims and count as touching.
+const HIDDEN_GAP_MM: f64 = 5.0;
+/// Gaps up to this wide are too narrow to use and need a filler panel.
+pub(crate) const FILLER_MAX_GAP_MM: f64 = 50.0;
+/// How far a back face may stand off the wall or module behind it (service void).
+const MAX_BACK_GAP_MM: f64 = 50.0;
+/// Cosine above which two faces count as parallel.
+const PARALLEL_COS: f64 = 0.999;
+
+/// Everything but decor forms runs.
+pub(crate) fn is_fitted(fp: &Footprint) -> bool {
+    !matches!(fp.kind, LayoutObjectKind::Decor)
+}
+
+/// Units that stand free by design.
+fn is_freestanding(fp: &Footprint) -> bool {
+    fp.is_island() || fp.is_peninsula()
+}
+
+/// Midpoint of the back face (the `position_mm` side).
+fn back_midpoint(fp: &Footprint) -> Vec2 {
+    fp.obb.center - fp.obb.axis_v().scale(fp.obb.half_depth)
+}
+
+/// Side faces as `(outward normal, start, end)`.
+fn side_faces(fp: &Footprint) -> [(Vec2, Vec2, Vec2); 2] {
+    let u = fp.obb.axis_u;
+    let v = fp.obb.axis_v().scale(fp.obb.half_depth);
+    let right = fp.obb.center + u.scale(fp.obb.half_width);
+    let left = fp.obb.center - u.scale(fp.obb.half_width);
+    [(u, right - v, right + v), (u.scale(-1.0), left - v, left + v)]
+}
+
+fn backed_by_wall(fp: &Footprint, walls: &[Wall]) -> bool {
+    let (back, front) = (back_midpoint(fp), fp.obb.axis_v());
+    walls.iter().any(|wall| {
+        wall.inward.dot(front) > PARALLEL_COS && point_segment_distance(back, wall.start, wall.end) <= MAX_BACK_GAP_MM
+    })
+}
+
+/// Whether the back of `fp` rests against a side face of `other`.
+fn backed_by_side(fp: &Footprint, other: &Footprint) -> bool {
+    let (back, front) = (back_midpoint(fp), fp.obb.axis_v());
+    fp.overlaps_vertically(other)
+        && side_faces(other).into_iter().any(|(normal, start, end)| {
+            normal.dot(front) > PARALLEL_COS && point_segment_distance(back, start, end) <= MAX_BACK_GAP_MM
+        })
+}
+
+/// Clear distance between the side faces of two parallel objects standing next to each other in
+/// a run, or `None` when they are not side by side.
+fn side_gap(a: &Footprint, b: &Footprint) -> Option<f64> {
+    if !a.overlaps_vertically(b) || a.obb.axis_u.dot(b.obb.axis_u).abs() < PARALLEL_COS {
+        return None;
+    }
+    let (u, v) = (a.obb.axis_u, a.obb.axis_v());
+    let ((av0, av1), (bv0, bv1)) = (a.obb.project(v), b.obb.project(v));
+    if av0.max(bv0) >= av1.min(bv1) - HIDDEN_GAP_MM {
+        // Not level with each other: one stands in front of the other.
+        return None;
+    }
+    let ((a0, a1), (b0, b1)) = (a.obb.project(u), b.obb.project(u));
+    Some((b0 - a1).max(a0 - b1))
+}
+
+/// Every module must either have its back against a wall or a module's side, or stand in a run
+/// next to one that does. Islands and peninsulas are exempt and anchor their run.
+pub struct FloatingObject;
+
+impl Constraint for FloatingObject {
+    fn id(&self) -> &'static str {
+        "floating_object"
+    }
+
+    fn category(&self) -> ConstraintCategory {
+        ConstraintCategory::Placement
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn evaluate(&self, ctx: &ConstraintContext, violations: &mut Vec<Violation>) {
+        let footprints = ctx.footprints;
+        let mut supported: Vec<bool> = footprints
+            .iter()
+            .map(|fp| is_fitted(fp) && (is_freestanding(fp) || backed_by_wall(fp, &ctx.outline.walls)))
+            .collect();
+        // The broad phase keeps gaps strictly below its margin; the limits here are inclusive.
+        for (i, j) in candidate_pairs(footprints, &ValidationScope::All, MAX_BACK_GAP_MM as i32 + 1) {
+            let (a, b) = (&footprints[i], &footprints[j]);
+            if !is_fitted(a) || !is_fitted(b) {
+                continue;
+            }
+            supported[i] |= backed_by_side(a, b);
+            supported[j] |= backed_by_side(b, a);
+        }
+
+        // Support spreads along runs, so the whole run needs only one backed member.
+        let neighbours: Vec<(usize, usize)> = candidate_pairs(footprints, &ValidationScope::All, FILLER_MAX_GAP_MM as i32 + 1)
+            .into_iter()
+            .filter(|&(i, j)| is_fitted(&footprints[i]) && is_fitted(&footprints[j]))
+            .filter(|&(i, j)| side_gap(&footprints[i], &footprints[j]).is_some_and(|gap| gap <= FILLER_MAX_GAP_MM))
+            .collect();
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for &(i, j) in &neighbours {
+                if supported[i] != supported[j] {
+                    supported[i] = true;
+                    supported[j] = true;
+                    changed = true;
+                }
+            }
+        }
+
+        for (fp, supported) in footprints.iter().zip(supported) {
+            // An object already outside the room is reported as such.
+            let inside = ctx.outline.contains(&fp.obb);
+            if matches!(fp.kind, LayoutObjectKind::Module) && !supported && inside && ctx.scope.includes(&fp.id) {
+                violations.push(self.violation(
+                    "layout.floating_object",
+                    "module must stand against a wall or in a run",
+                    vec![fp.id.clone()],
+                ));
+            }
+        }
+    }
+}
+
+/// Gaps between neighbouring units, or between a unit's side and a wall, that are too narrow to
+/// use but too wide to hide. `details.gap_mm` carries the width, and `details.wall_id` the wall
+/// for gaps at the end of a run.
+pub struct FillerGap;
+
+impl FillerGap {
+    fn gap(&self, object_ids: Vec<String>, gap: f64, wall_id: Option<&str>) -> Violation {
+        let mut details = HashMap::new();
+        details.insert("gap_mm".to_string(), Value::from(gap.round() as i64));
+        if let Some(wall_id) = wall_id {
+            details.insert("wall_id".to_string(), Value::String(wall_id.to_string()));
+        }
+        self.violation("layout.filler_required", "gap needs a filler panel", object_ids)
+            .with_details(details)
+    }
+}
+
+impl Constraint for FillerGap {
+    fn id(&self) -> &'static str {
+        "filler_gap"
+    }
+
+    fn category(&self) -> ConstraintCategory {
+        ConstraintCategory::Placement
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn evaluate(&self, ctx: &ConstraintContext, violations: &mut Vec<Violation>) {
+        let footprints = ctx.footprints;
+        let needs_filler = |gap: f64| gap > HIDDEN_GAP_MM && gap <= FILLER_MAX_GAP_MM;
+
+        for (i, j) in candidate_pairs(footprints, ctx.scope, FILLER_MAX_GAP_MM as i32 + 1) {
+            let (a, b) = (&footprints[i], &footprints[j]);
+            if !is_fitted(a) || !is_fitted(b) {
+                continue;
+            }
+            if let Some(gap) = side_gap(a, b).filter(|gap| needs_filler(*gap)) {
+                violations.push(self.gap(vec![a.id.clone(), b.id.clone()], gap, None));
+            }
+        }
+
+        for fp in footprints.iter().filter(|fp| is_fitted(fp) && ctx.scope.includes(&fp.id)) {
+            for (normal, start, end) in side_faces(fp) {
+                let middle = (start + end).scale(0.5);
+                for wall in &ctx.outline.walls {
+                    if wall.inward.dot(normal) > -PARALLEL_COS {
+                        continue;
+                    }
+                    let gap = point_segment_distance(middle, wall.start, wall.end);
+                    if needs_filler(gap) {
+                        violations.push(self.gap(vec![fp.id.clone()], gap, Some(&wall.id)));
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,85 +1,97 @@
-use crate::constraints::Footprint;
-use crate::model::kitchen_state::KitchenState;
-use crate::model::room::Point2Mm;
-use crate::model::violation::Violation;
+use crate::constraints::outline::RoomOutline;
+use crate::constraints::{has_tag, Constraint, ConstraintCategory, ConstraintContext, ValidationScope};
+use crate::model::room::{Point2Mm, UtilityKind, UtilityPoint};
+use crate::model::violation::{Severity, Violation};
+use serde_json::Value;
+use std::collections::HashMap;
 
-fn utility_position(room: &crate::model::room::SizeMm, util: &crate::model::room::UtilityPoint) -> Option<Point2Mm> {
+/// Appliances that need a power point within reach.
+const POWERED_APPLIANCE_TAGS: &[&str] = &["oven", "dishwasher", "fridge", "refrigerator", "microwave"];
+
+fn utility_position(outline: &RoomOutline, util: &UtilityPoint) -> Option<Point2Mm> {
     if let Some(pos) = &util.position_mm {
         return Some(pos.clone());
     }
 
     let wall_id = util.wall_id.as_deref()?;
     let offset = util.offset_mm?;
-
-    match wall_id {
-        "south" => Some(Point2Mm { x: offset, y: 0 }),
-        "north" => Some(Point2Mm { x: offset, y: room.depth }),
-        "west" => Some(Point2Mm { x: 0, y: offset }),
-        "east" => Some(Point2Mm { x: room.width, y: offset }),
-        _ => None,
-    }
+    outline.wall_point(wall_id, offset)
 }
 
-fn distance_mm(a: &Point2Mm, b: &Point2Mm) -> f64 {
+pub(crate) fn distance_mm(a: &Point2Mm, b: &Point2Mm) -> f64 {
     let dx = (a.x - b.x) as f64;
     let dy = (a.y - b.y) as f64;
     (dx * dx + dy * dy).sqrt()
 }
 
-fn has_tag(tags: &Option<Vec<String>>, expected: &[&str]) -> bool {
-    let Some(list) = tags else {
-        return false;
-    };
-    list.iter()
-        .any(|tag| expected.iter().any(|e| tag.eq_ignore_ascii_case(e)))
+/// Positions and zone radii of the room's utilities matching `kinds`.
+pub(crate) fn utility_points(ctx: &ConstraintContext, kinds: &[UtilityKind]) -> Vec<(Point2Mm, i32)> {
+    ctx.state
+        .room
+        .utilities
+        .iter()
+        .filter(|u| kinds.contains(&u.kind))
+        .filter_map(|u| utility_position(ctx.outline, u).map(|p| (p, u.zone_radius_mm)))
+        .collect()
 }
 
-pub fn check_plumbing_power(state: &KitchenState, footprints: &[Footprint], violations: &mut Vec<Violation>) {
-    let room = &state.room.size_mm;
-    let utilities = &state.room.utilities;
-
-    let water_points: Vec<(Point2Mm, i32)> = utilities
+fn within_any(anchor: &Point2Mm, points: &[(Point2Mm, i32)]) -> bool {
+    points
         .iter()
-        .filter(|u| matches!(u.kind, crate::model::room::UtilityKind::Water | crate::model::room::UtilityKind::Drain))
-        .filter_map(|u| utility_position(room, u).map(|p| (p, u.zone_radius_mm)))
-        .collect();
+        .any(|(pos, radius)| distance_mm(anchor, pos) <= *radius as f64)
+}
 
-    let vent_points: Vec<(Point2Mm, i32)> = utilities
-        .iter()
-        .filter(|u| matches!(u.kind, crate::model::room::UtilityKind::Vent))
-        .filter_map(|u| utility_position(room, u).map(|p| (p, u.zone_radius_mm)))
-        .collect();
-
-    for fp in footprints {
-        if has_tag(&fp.tags, &["sink"]) {
-            let mut ok = false;
-            for (pos, radius) in &water_points {
-                if distance_mm(&fp.anchor, pos) <= *radius as f64 {
-                    ok = true;
-                    break;
-                }
-            }
+/// Sinks outside the zone of every water and drain point.
+pub struct SinkNearWater;
 
-            if !ok {
-                violations.push(Violation::error(
+impl Constraint for SinkNearWater {
+    fn id(&self) -> &'static str {
+        "sink_near_water"
+    }
+
+    fn category(&self) -> ConstraintCategory {
+        ConstraintCategory::Utility
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn evaluate(&self, ctx: &ConstraintContext, violations: &mut Vec<Violation>) {
+        let water_points = utility_points(ctx, &[UtilityKind::Water, UtilityKind::Drain]);
+        for fp in ctx.footprints.iter().filter(|fp| ctx.scope.includes(&fp.id)) {
+            if has_tag(&fp.tags, &["sink"]) && !within_any(&fp.anchor, &water_points) {
+                violations.push(self.violation(
                     "layout.sink_near_water",
                     "sink should be placed near water/drain utilities",
                     vec![fp.id.clone()],
                 ));
             }
         }
+    }
+}
 
-        if has_tag(&fp.tags, &["hob", "cooktop"]) {
-            let mut ok = false;
-            for (pos, radius) in &vent_points {
-                if distance_mm(&fp.anchor, pos) <= *radius as f64 {
-                    ok = true;
-                    break;
-                }
-            }
+/// Hobs outside the zone of every vent.
+pub struct HobNearVent;
+
+impl Constraint for HobNearVent {
+    fn id(&self) -> &'static str {
+        "hob_near_vent"
+    }
+
+    fn category(&self) -> ConstraintCategory {
+        ConstraintCategory::Utility
+    }
 
-            if !ok {
-                violations.push(Violation::warning(
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn evaluate(&self, ctx: &ConstraintContext, violations: &mut Vec<Violation>) {
+        let vent_points = utility_points(ctx, &[UtilityKind::Vent]);
+        for fp in ctx.footprints.iter().filter(|fp| ctx.scope.includes(&fp.id)) {
+            if has_tag(&fp.tags, &["hob", "cooktop"]) && !within_any(&fp.anchor, &vent_points) {
+                violations.push(self.violation(
                     "layout.hob_near_vent",
                     "cooktop should be placed near a vent",
                     vec![fp.id.clone()],
@@ -88,3 +100,143 @@ pub fn check_plumbing_power(state: &KitchenState, footprints: &[Footprint], viol
         }
     }
 }
+
+/// Gas hobs (tagged `gas_hob`, or `hob`/`cooktop` together with `gas`) outside the zone of every
+/// gas point.
+pub struct GasHobNearGas;
+
+impl Constraint for GasHobNearGas {
+    fn id(&self) -> &'static str {
+        "gas_hob_near_gas"
+    }
+
+    fn category(&self) -> ConstraintCategory {
+        ConstraintCategory::Utility
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn evaluate(&self, ctx: &ConstraintContext, violations: &mut Vec<Violation>) {
+        let gas_points = utility_points(ctx, &[UtilityKind::Gas]);
+        for fp in ctx.footprints.iter().filter(|fp| ctx.scope.includes(&fp.id)) {
+            let gas_hob = has_tag(&fp.tags, &["gas_hob"])
+                || (has_tag(&fp.tags, &["hob", "cooktop"]) && has_tag(&fp.tags, &["gas"]));
+            if gas_hob && !within_any(&fp.anchor, &gas_points) {
+                violations.push(self.violation(
+                    "layout.gas_hob_near_gas",
+                    "gas hob should be placed near a gas point",
+                    vec![fp.id.clone()],
+                ));
+            }
+        }
+    }
+}
+
+/// Ovens, dishwashers, fridges and microwaves outside the zone of every power point.
+pub struct ApplianceNearPower;
+
+impl Constraint for ApplianceNearPower {
+    fn id(&self) -> &'static str {
+        "appliance_near_power"
+    }
+
+    fn category(&self) -> ConstraintCategory {
+        ConstraintCategory::Utility
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn evaluate(&self, ctx: &ConstraintContext, violations: &mut Vec<Violation>) {
+        let power_points = utility_points(ctx, &[UtilityKind::Power]);
+        for fp in ctx.footprints.iter().filter(|fp| ctx.scope.includes(&fp.id)) {
+            if has_tag(&fp.tags, POWERED_APPLIANCE_TAGS) && !within_any(&fp.anchor, &power_points) {
+                violations.push(self.violation(
+                    "layout.appliance_near_power",
+                    "appliance should be placed near a power point",
+                    vec![fp.id.clone()],
+                ));
+            }
+        }
+    }
+}
+
+/// Gas, water/drain and power points closer to each other than the ruleset allows. These are
+/// properties of the room, so they are only reported by full runs; `details` carries the
+/// `utility_ids`, `distance_mm` and `min_distance_mm`.
+pub struct UtilitySeparation;
+
+impl Constraint for UtilitySeparation {
+    fn id(&self) -> &'static str {
+        "utility_separation"
+    }
+
+    fn category(&self) -> ConstraintCategory {
+        ConstraintCategory::Utility
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn evaluate(&self, ctx: &ConstraintContext, violations: &mut Vec<Violation>) {
+        if !matches!(ctx.scope, ValidationScope::All) {
+            return;
+        }
+
+        let located = |kinds: &[UtilityKind]| -> Vec<(&UtilityPoint, Point2Mm)> {
+            ctx.state
+                .room
+                .utilities
+                .iter()
+                .filter(|u| kinds.contains(&u.kind))
+                .filter_map(|u| utility_position(ctx.outline, u).map(|p| (u, p)))
+                .collect()
+        };
+        let gas = located(&[UtilityKind::Gas]);
+        let water = located(&[UtilityKind::Water, UtilityKind::Drain]);
+        let power = located(&[UtilityKind::Power]);
+
+        let pairs = [
+            (
+                &gas,
+                &water,
+                ctx.rules.min_gas_water_separation_mm,
+                "room.gas_water_separation",
+                "gas point is too close to a water point",
+            ),
+            (
+                &gas,
+                &power,
+                ctx.rules.min_gas_power_separation_mm,
+                "room.gas_power_separation",
+                "gas point is too close to a power point",
+            ),
+            (
+                &water,
+                &power,
+                ctx.rules.min_water_power_separation_mm,
+                "room.water_power_separation",
+                "water point is too close to a power point",
+            ),
+        ];
+        for (first, second, min_distance, code, message) in pairs {
+            for (a, a_pos) in first {
+                for (b, b_pos) in second {
+                    let distance = distance_mm(a_pos, b_pos);
+                    if distance >= min_distance as f64 {
+                        continue;
+                    }
+                    let mut details = HashMap::new();
+                    details.insert("utility_ids".to_string(), Value::from(vec![a.id.clone(), b.id.clone()]));
+                    details.insert("distance_mm".to_string(), Value::from(distance.round() as i64));
+                    details.insert("min_distance_mm".to_string(), Value::from(min_distance));
+                    violations.push(self.violation(code, message, vec![]).with_details(details));
+                }
+            }
+        }
+    }
+}
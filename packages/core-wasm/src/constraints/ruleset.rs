@@ -0,0 +1,57 @@
+use crate::model::ruleset::Ruleset;
+use crate::model::violation::Violation;
+use serde_json::Value;
+use std::collections::HashMap;
+
+const BUILTIN_RULESETS_JSON: &str = include_str!("rulesets.json");
+
+/// Ruleset used when the project does not name one.
+pub const DEFAULT_RULESET_VERSION: &str = "pricing_ruleset_v1";
+
+/// Known rulesets, looked up by version.
+#[derive(Debug, Clone)]
+pub struct RulesetCatalog {
+    rulesets: Vec<Ruleset>,
+}
+
+impl RulesetCatalog {
+    /// Rulesets shipped with the core.
+    pub fn builtin() -> Self {
+        Self::from_json(BUILTIN_RULESETS_JSON).expect("built-in rulesets are valid JSON")
+    }
+
+    /// Parses a JSON array of rulesets.
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        Ok(Self {
+            rulesets: serde_json::from_str(json)?,
+        })
+    }
+
+    /// Adds `other`'s rulesets, replacing any with the same version.
+    pub fn extend(&mut self, other: RulesetCatalog) {
+        for ruleset in other.rulesets {
+            self.rulesets.retain(|r| r.version != ruleset.version);
+            self.rulesets.push(ruleset);
+        }
+    }
+
+    pub fn get(&self, version: &str) -> Option<&Ruleset> {
+        self.rulesets.iter().find(|r| r.version == version)
+    }
+
+    /// The ruleset for `version` (or the default when unset), or a `ruleset.unknown_version`
+    /// violation listing the versions that are available.
+    pub fn resolve(&self, version: Option<&str>) -> Result<&Ruleset, Vec<Violation>> {
+        let version = version.unwrap_or(DEFAULT_RULESET_VERSION);
+        self.get(version).ok_or_else(|| {
+            let mut details = HashMap::new();
+            details.insert("ruleset_version".to_string(), Value::String(version.to_string()));
+            details.insert(
+                "known_versions".to_string(),
+                Value::from(self.rulesets.iter().map(|r| r.version.clone()).collect::<Vec<_>>()),
+            );
+            vec![Violation::error("ruleset.unknown_version", "ruleset_version is not known", vec![])
+                .with_details(details)]
+        })
+    }
+}
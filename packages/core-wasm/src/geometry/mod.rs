@@ -1,4 +1,9 @@
 pub mod aabb;
 pub mod obb;
+pub mod polygon;
+pub mod sector;
 pub mod sweep;
 pub mod units;
+
+/// Tolerance in mm for float comparisons; keeps touching shapes from counting as overlapping.
+pub(crate) const EPSILON_MM: f64 = 1e-6;
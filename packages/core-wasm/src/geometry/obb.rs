@@ -1 +1,289 @@
-pub struct Obb;
+use crate::geometry::aabb::Aabb;
+use crate::geometry::EPSILON_MM;
+use std::ops::{Add, Sub};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Vec2 {
+    pub x: f64,
+    pub y: f64,
+}
+
+impl Vec2 {
+    pub fn new(x: f64, y: f64) -> Self {
+        Self { x, y }
+    }
+
+    pub fn dot(self, other: Vec2) -> f64 {
+        self.x * other.x + self.y * other.y
+    }
+
+    pub fn scale(self, factor: f64) -> Vec2 {
+        Vec2::new(self.x * factor, self.y * factor)
+    }
+
+    pub fn length(self) -> f64 {
+        self.dot(self).sqrt()
+    }
+}
+
+impl Add for Vec2 {
+    type Output = Vec2;
+
+    fn add(self, other: Vec2) -> Vec2 {
+        Vec2::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl Sub for Vec2 {
+    type Output = Vec2;
+
+    fn sub(self, other: Vec2) -> Vec2 {
+        Vec2::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+/// Unit direction for a rotation in degrees (counter-clockwise), exact for multiples of 90°.
+pub fn direction(rotation_deg: i32) -> Vec2 {
+    match rotation_deg.rem_euclid(360) {
+        0 => Vec2::new(1.0, 0.0),
+        90 => Vec2::new(0.0, 1.0),
+        180 => Vec2::new(-1.0, 0.0),
+        270 => Vec2::new(0.0, -1.0),
+        deg => {
+            let rad = (deg as f64).to_radians();
+            Vec2::new(rad.cos(), rad.sin())
+        }
+    }
+}
+
+/// Oriented rectangle in plan view.
+#[derive(Debug, Clone)]
+pub struct Obb {
+    pub center: Vec2,
+    pub half_width: f64,
+    pub half_depth: f64,
+    /// Unit vector of the local width axis; the depth axis is its counter-clockwise normal.
+    pub axis_u: Vec2,
+}
+
+impl Obb {
+    pub fn new(center: Vec2, width: f64, depth: f64, rotation_deg: i32) -> Self {
+        Self {
+            center,
+            half_width: width / 2.0,
+            half_depth: depth / 2.0,
+            axis_u: direction(rotation_deg),
+        }
+    }
+
+    /// Rectangle whose width runs along the unit vector `axis_u`.
+    pub fn from_axis(center: Vec2, width: f64, depth: f64, axis_u: Vec2) -> Self {
+        Self {
+            center,
+            half_width: width / 2.0,
+            half_depth: depth / 2.0,
+            axis_u,
+        }
+    }
+
+    pub fn from_aabb(aabb: &Aabb) -> Self {
+        let center = Vec2::new(
+            (aabb.min_x as f64 + aabb.max_x as f64) / 2.0,
+            (aabb.min_y as f64 + aabb.max_y as f64) / 2.0,
+        );
+        Self::new(
+            center,
+            (aabb.max_x - aabb.min_x) as f64,
+            (aabb.max_y - aabb.min_y) as f64,
+            0,
+        )
+    }
+
+    pub fn axis_v(&self) -> Vec2 {
+        Vec2::new(-self.axis_u.y, self.axis_u.x)
+    }
+
+    /// Corners in counter-clockwise order.
+    pub fn corners(&self) -> [Vec2; 4] {
+        let u = self.axis_u.scale(self.half_width);
+        let v = self.axis_v().scale(self.half_depth);
+        let c = self.center;
+        [c - u - v, c + u - v, c + u + v, c - u + v]
+    }
+
+    pub fn is_axis_aligned(&self) -> bool {
+        self.axis_u.x.abs() < EPSILON_MM || self.axis_u.y.abs() < EPSILON_MM
+    }
+
+    /// Smallest integer box containing the rectangle.
+    pub fn aabb(&self) -> Aabb {
+        let corners = self.corners();
+        let (mut min_x, mut min_y) = (f64::MAX, f64::MAX);
+        let (mut max_x, mut max_y) = (f64::MIN, f64::MIN);
+        for p in corners {
+            min_x = min_x.min(p.x);
+            min_y = min_y.min(p.y);
+            max_x = max_x.max(p.x);
+            max_y = max_y.max(p.y);
+        }
+        Aabb::from_min_max(
+            (min_x + EPSILON_MM).floor() as i32,
+            (min_y + EPSILON_MM).floor() as i32,
+            (max_x - EPSILON_MM).ceil() as i32,
+            (max_y - EPSILON_MM).ceil() as i32,
+        )
+    }
+
+    pub fn area_mm2(&self) -> f64 {
+        4.0 * self.half_width * self.half_depth
+    }
+
+    /// Interval the rectangle covers along `axis`.
+    pub fn project(&self, axis: Vec2) -> (f64, f64) {
+        let center = self.center.dot(axis);
+        let radius = self.half_width * self.axis_u.dot(axis).abs() + self.half_depth * self.axis_v().dot(axis).abs();
+        (center - radius, center + radius)
+    }
+
+    /// Separating-axis test. Rectangles that only touch do not intersect.
+    pub fn intersects(&self, other: &Obb) -> bool {
+        [self.axis_u, self.axis_v(), other.axis_u, other.axis_v()]
+            .into_iter()
+            .all(|axis| {
+                let (a_min, a_max) = self.project(axis);
+                let (b_min, b_max) = other.project(axis);
+                a_min < b_max - EPSILON_MM && b_min < a_max - EPSILON_MM
+            })
+    }
+
+    /// Whether the segment `a`-`b` passes through the interior; running along an edge does not count.
+    pub fn intersects_segment(&self, a: Vec2, b: Vec2) -> bool {
+        let along = b - a;
+        let length = along.length();
+        if length < EPSILON_MM {
+            return false;
+        }
+        let normal = Vec2::new(-along.y / length, along.x / length);
+        [self.axis_u, self.axis_v(), normal].into_iter().all(|axis| {
+            let (min, max) = self.project(axis);
+            let (pa, pb) = (a.dot(axis), b.dot(axis));
+            pa.min(pb) < max - EPSILON_MM && min < pa.max(pb) - EPSILON_MM
+        })
+    }
+
+    /// Shortest distance to the segment `a`-`b`; 0 when it touches or crosses the rectangle.
+    pub fn segment_distance(&self, a: Vec2, b: Vec2) -> f64 {
+        if self.intersects_segment(a, b) {
+            return 0.0;
+        }
+        let (p, q) = self.closest_points_to_segment(a, b);
+        (q - p).length()
+    }
+
+    pub fn contains_point(&self, p: Vec2) -> bool {
+        let d = p - self.center;
+        d.dot(self.axis_u).abs() <= self.half_width + EPSILON_MM && d.dot(self.axis_v()).abs() <= self.half_depth + EPSILON_MM
+    }
+
+    /// Distance from `p` to the rectangle; 0 inside it.
+    pub fn point_distance(&self, p: Vec2) -> f64 {
+        let d = p - self.center;
+        let du = (d.dot(self.axis_u).abs() - self.half_width).max(0.0);
+        let dv = (d.dot(self.axis_v()).abs() - self.half_depth).max(0.0);
+        Vec2::new(du, dv).length()
+    }
+
+    pub fn contains(&self, other: &Obb) -> bool {
+        other.corners().into_iter().all(|p| self.contains_point(p))
+    }
+
+    /// Shortest distance between the two rectangles; 0 when they touch or overlap.
+    pub fn distance(&self, other: &Obb) -> f64 {
+        if self.intersects(other) {
+            return 0.0;
+        }
+        let (p, q) = self.closest_points(other);
+        (q - p).length()
+    }
+
+    /// Nearest pair of boundary points, on `self` and on `other`. Where parallel faces are
+    /// equally near, the pair lies in the middle of the stretch they face each other along.
+    /// Meaningful only when the rectangles do not overlap.
+    pub fn closest_points(&self, other: &Obb) -> (Vec2, Vec2) {
+        if self.is_axis_aligned() && other.is_axis_aligned() {
+            let (x, y) = (Vec2::new(1.0, 0.0), Vec2::new(0.0, 1.0));
+            let (px, qx) = nearest_in_intervals(self.project(x), other.project(x));
+            let (py, qy) = nearest_in_intervals(self.project(y), other.project(y));
+            return (Vec2::new(px, py), Vec2::new(qx, qy));
+        }
+        let a = self.corners();
+        let candidates = (0..4).map(|i| {
+            let (p, q) = other.closest_points_to_segment(a[i], a[(i + 1) % 4]);
+            (q, p)
+        });
+        average_nearest(candidates).unwrap_or((a[0], other.center))
+    }
+
+    /// Nearest pair of points, on the rectangle's boundary and on the segment `a`-`b`, centred
+    /// like `closest_points`. Meaningful only when the segment does not cross the rectangle.
+    pub fn closest_points_to_segment(&self, a: Vec2, b: Vec2) -> (Vec2, Vec2) {
+        let corners = self.corners();
+        let candidates = (0..4).flat_map(|i| {
+            let (c0, c1) = (corners[i], corners[(i + 1) % 4]);
+            [
+                (corners[i], closest_point_on_segment(corners[i], a, b)),
+                (closest_point_on_segment(a, c0, c1), a),
+                (closest_point_on_segment(b, c0, c1), b),
+            ]
+        });
+        average_nearest(candidates).unwrap_or((corners[0], a))
+    }
+}
+
+/// Nearest coordinates of two intervals: facing ends when apart, the middle of the overlap
+/// otherwise.
+fn nearest_in_intervals((a0, a1): (f64, f64), (b0, b1): (f64, f64)) -> (f64, f64) {
+    if a1 <= b0 {
+        (a1, b0)
+    } else if b1 <= a0 {
+        (a0, b1)
+    } else {
+        let middle = (a0.max(b0) + a1.min(b1)) / 2.0;
+        (middle, middle)
+    }
+}
+
+/// Mean of the point pairs whose separation ties for the smallest.
+fn average_nearest(candidates: impl Iterator<Item = (Vec2, Vec2)>) -> Option<(Vec2, Vec2)> {
+    let candidates: Vec<(Vec2, Vec2)> = candidates.collect();
+    let nearest = candidates
+        .iter()
+        .map(|(p, q)| (*q - *p).length())
+        .fold(f64::MAX, f64::min);
+    let ties: Vec<&(Vec2, Vec2)> = candidates
+        .iter()
+        .filter(|(p, q)| (*q - *p).length() <= nearest + EPSILON_MM)
+        .collect();
+    if ties.is_empty() {
+        return None;
+    }
+    let scale = 1.0 / ties.len() as f64;
+    let (p, q) = ties
+        .iter()
+        .fold((Vec2::new(0.0, 0.0), Vec2::new(0.0, 0.0)), |(p, q), (tp, tq)| (p + *tp, q + *tq));
+    Some((p.scale(scale), q.scale(scale)))
+}
+
+pub fn point_segment_distance(p: Vec2, a: Vec2, b: Vec2) -> f64 {
+    (p - closest_point_on_segment(p, a, b)).length()
+}
+
+pub fn closest_point_on_segment(p: Vec2, a: Vec2, b: Vec2) -> Vec2 {
+    let ab = b - a;
+    let len2 = ab.dot(ab);
+    if len2 <= 0.0 {
+        return a;
+    }
+    let t = ((p - a).dot(ab) / len2).clamp(0.0, 1.0);
+    a + ab.scale(t)
+}
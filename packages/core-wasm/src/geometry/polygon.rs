@@ -0,0 +1,172 @@
+use crate::geometry::aabb::Aabb;
+use crate::geometry::obb::{point_segment_distance, Obb, Vec2};
+use crate::geometry::EPSILON_MM;
+
+/// Simple polygon in plan view. Vertices may wind either way; the last connects to the first.
+#[derive(Debug, Clone)]
+pub struct Polygon {
+    pub points: Vec<Vec2>,
+}
+
+impl Polygon {
+    pub fn new(points: Vec<Vec2>) -> Self {
+        Self { points }
+    }
+
+    pub fn from_aabb(aabb: &Aabb) -> Self {
+        let (min_x, min_y) = (aabb.min_x as f64, aabb.min_y as f64);
+        let (max_x, max_y) = (aabb.max_x as f64, aabb.max_y as f64);
+        Self::new(vec![
+            Vec2::new(min_x, min_y),
+            Vec2::new(max_x, min_y),
+            Vec2::new(max_x, max_y),
+            Vec2::new(min_x, max_y),
+        ])
+    }
+
+    pub fn edges(&self) -> impl Iterator<Item = (Vec2, Vec2)> + '_ {
+        let n = self.points.len();
+        (0..n).map(move |i| (self.points[i], self.points[(i + 1) % n]))
+    }
+
+    /// Positive for counter-clockwise winding.
+    pub fn signed_area_mm2(&self) -> f64 {
+        self.edges().map(|(a, b)| a.x * b.y - b.x * a.y).sum::<f64>() / 2.0
+    }
+
+    pub fn area_mm2(&self) -> f64 {
+        self.signed_area_mm2().abs()
+    }
+
+    pub fn perimeter_mm(&self) -> f64 {
+        self.edges().map(|(a, b)| (b - a).length()).sum()
+    }
+
+    /// Smallest integer box containing the polygon.
+    pub fn bounds(&self) -> Aabb {
+        let (mut min_x, mut min_y) = (f64::MAX, f64::MAX);
+        let (mut max_x, mut max_y) = (f64::MIN, f64::MIN);
+        for p in &self.points {
+            min_x = min_x.min(p.x);
+            min_y = min_y.min(p.y);
+            max_x = max_x.max(p.x);
+            max_y = max_y.max(p.y);
+        }
+        Aabb::from_min_max(
+            (min_x + EPSILON_MM).floor() as i32,
+            (min_y + EPSILON_MM).floor() as i32,
+            (max_x - EPSILON_MM).ceil() as i32,
+            (max_y - EPSILON_MM).ceil() as i32,
+        )
+    }
+
+    /// At least three vertices, no zero-length edges, and no edge meeting another except its
+    /// neighbours at shared vertices.
+    pub fn is_simple(&self) -> bool {
+        let n = self.points.len();
+        if n < 3 || self.area_mm2() <= EPSILON_MM {
+            return false;
+        }
+        let edges: Vec<(Vec2, Vec2)> = self.edges().collect();
+        if edges.iter().any(|(a, b)| (*b - *a).length() <= EPSILON_MM) {
+            return false;
+        }
+        for i in 0..n {
+            for j in i + 1..n {
+                let adjacent = j == i + 1 || (i == 0 && j == n - 1);
+                if !adjacent && segments_touch(edges[i], edges[j]) {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
+    /// Points on the boundary count as inside.
+    pub fn contains_point(&self, p: Vec2) -> bool {
+        let mut inside = false;
+        for (a, b) in self.edges() {
+            if point_segment_distance(p, a, b) <= EPSILON_MM {
+                return true;
+            }
+            if (a.y > p.y) != (b.y > p.y) {
+                let x = a.x + (p.y - a.y) / (b.y - a.y) * (b.x - a.x);
+                if p.x < x {
+                    inside = !inside;
+                }
+            }
+        }
+        inside
+    }
+
+    /// Part of the polygon inside the rectangle. The polygon may be concave; the result can then
+    /// contain zero-width bridges, which do not affect its area.
+    pub fn clip_to_obb(&self, obb: &Obb) -> Polygon {
+        let corners = obb.corners();
+        (0..4).fold(self.clone(), |clipped, i| {
+            let (a, b) = (corners[i], corners[(i + 1) % 4]);
+            let along = b - a;
+            clipped.clip_half_plane(a, Vec2::new(-along.y, along.x))
+        })
+    }
+
+    /// Part of the polygon on the side of the line through `origin` that `normal` points to.
+    pub fn clip_half_plane(&self, origin: Vec2, normal: Vec2) -> Polygon {
+        let side = |p: Vec2| (p - origin).dot(normal);
+        let n = self.points.len();
+        let mut clipped = Vec::with_capacity(n + 1);
+        for j in 0..n {
+            let (p, q) = (self.points[j], self.points[(j + 1) % n]);
+            let (sp, sq) = (side(p), side(q));
+            if sp >= 0.0 {
+                clipped.push(p);
+            }
+            if (sp >= 0.0) != (sq >= 0.0) {
+                clipped.push(p + (q - p).scale(sp / (sp - sq)));
+            }
+        }
+        Polygon::new(clipped)
+    }
+
+    /// Distance from `p` to the polygon; 0 on or inside it.
+    pub fn distance_to_point(&self, p: Vec2) -> f64 {
+        if self.contains_point(p) {
+            return 0.0;
+        }
+        self.edges()
+            .map(|(a, b)| point_segment_distance(p, a, b))
+            .fold(f64::MAX, f64::min)
+    }
+
+    pub fn overlap_area_mm2(&self, obb: &Obb) -> f64 {
+        self.clip_to_obb(obb).area_mm2()
+    }
+
+    /// Whether the polygon and rectangle share interior; touching edges do not count.
+    pub fn intersects_obb(&self, obb: &Obb) -> bool {
+        self.overlap_area_mm2(obb) > EPSILON_MM
+    }
+
+    /// Whether the rectangle lies inside the polygon; it may rest against the walls. Checking
+    /// the corners alone is not enough for concave outlines, so no edge may cross the rectangle.
+    pub fn contains_obb(&self, obb: &Obb) -> bool {
+        obb.corners().into_iter().all(|p| self.contains_point(p))
+            && !self.edges().any(|(a, b)| obb.intersects_segment(a, b))
+    }
+}
+
+fn cross(u: Vec2, v: Vec2) -> f64 {
+    u.x * v.y - u.y * v.x
+}
+
+fn segments_touch((a, b): (Vec2, Vec2), (c, d): (Vec2, Vec2)) -> bool {
+    let (d1, d2) = (cross(d - c, a - c), cross(d - c, b - c));
+    let (d3, d4) = (cross(b - a, c - a), cross(b - a, d - a));
+    if d1 * d2 < 0.0 && d3 * d4 < 0.0 {
+        return true;
+    }
+    point_segment_distance(a, c, d) <= EPSILON_MM
+        || point_segment_distance(b, c, d) <= EPSILON_MM
+        || point_segment_distance(c, a, b) <= EPSILON_MM
+        || point_segment_distance(d, a, b) <= EPSILON_MM
+}
@@ -0,0 +1,33 @@
+use crate::geometry::obb::{Obb, Vec2};
+use crate::geometry::polygon::Polygon;
+use crate::geometry::EPSILON_MM;
+
+/// Circular sector of at most 180°, e.g. the floor area swept by a door leaf. It spans the
+/// unit directions `from` and `to` around `apex`.
+#[derive(Debug, Clone)]
+pub struct Sector {
+    pub apex: Vec2,
+    pub radius: f64,
+    pub from: Vec2,
+    pub to: Vec2,
+}
+
+impl Sector {
+    /// Orders the bounding directions counter-clockwise, so callers may pass them either way.
+    pub fn new(apex: Vec2, radius: f64, a: Vec2, b: Vec2) -> Self {
+        let counter_clockwise = a.x * b.y - a.y * b.x >= 0.0;
+        let (from, to) = if counter_clockwise { (a, b) } else { (b, a) };
+        Self { apex, radius, from, to }
+    }
+
+    /// Whether the rectangle reaches into the sector's interior.
+    pub fn intersects_obb(&self, obb: &Obb) -> bool {
+        if self.radius <= EPSILON_MM {
+            return false;
+        }
+        let inside_wedge = Polygon::new(obb.corners().to_vec())
+            .clip_half_plane(self.apex, Vec2::new(-self.from.y, self.from.x))
+            .clip_half_plane(self.apex, Vec2::new(self.to.y, -self.to.x));
+        inside_wedge.area_mm2() > EPSILON_MM && inside_wedge.distance_to_point(self.apex) < self.radius - EPSILON_MM
+    }
+}
@@ -1,3 +1,68 @@
-pub fn sweep() {
-    // placeholder
+use crate::geometry::aabb::Aabb;
+
+/// Sweep-and-prune broad phase.
+///
+/// Returns every index pair `(i, j)`, `i < j`, whose boxes come closer than `margin_mm` on both
+/// axes (with `margin_mm = 0` this is exactly [`Aabb::intersects`]). Pairs are sorted so callers
+/// see them in the same order as a nested `i < j` scan.
+pub fn sweep_pairs(boxes: &[Aabb], margin_mm: i32) -> Vec<(usize, usize)> {
+    let mut order: Vec<usize> = (0..boxes.len()).collect();
+    order.sort_by_key(|&i| (boxes[i].min_x, i));
+
+    let mut active: Vec<usize> = Vec::new();
+    let mut pairs = Vec::new();
+    for &i in &order {
+        let current = &boxes[i];
+        // Boxes are visited by ascending min_x, so anything ending before this one starts
+        // (plus margin) cannot pair with any later box either.
+        active.retain(|&a| boxes[a].max_x + margin_mm > current.min_x);
+        for &a in &active {
+            if near(&boxes[a], current, margin_mm) {
+                pairs.push((a.min(i), a.max(i)));
+            }
+        }
+        active.push(i);
+    }
+
+    pairs.sort_unstable();
+    pairs
+}
+
+/// Sweep-and-prune between two sets: every pair `(i, j)` of `left[i]` and `right[j]` that come
+/// closer than `margin_mm` on both axes, sorted. Boxes within the same set are never paired, so
+/// querying a few regions against many objects costs no more than the regions' neighbourhoods.
+pub fn sweep_pairs_between(left: &[Aabb], right: &[Aabb], margin_mm: i32) -> Vec<(usize, usize)> {
+    let mut order: Vec<(i32, bool, usize)> = left
+        .iter()
+        .enumerate()
+        .map(|(i, b)| (b.min_x, false, i))
+        .chain(right.iter().enumerate().map(|(j, b)| (b.min_x, true, j)))
+        .collect();
+    order.sort_unstable();
+
+    let (mut active_left, mut active_right): (Vec<usize>, Vec<usize>) = (Vec::new(), Vec::new());
+    let mut pairs = Vec::new();
+    for (_, is_right, i) in order {
+        if is_right {
+            let current = &right[i];
+            active_left.retain(|&a| left[a].max_x + margin_mm > current.min_x);
+            pairs.extend(active_left.iter().filter(|&&a| near(&left[a], current, margin_mm)).map(|&a| (a, i)));
+            active_right.push(i);
+        } else {
+            let current = &left[i];
+            active_right.retain(|&b| right[b].max_x + margin_mm > current.min_x);
+            pairs.extend(active_right.iter().filter(|&&b| near(current, &right[b], margin_mm)).map(|&b| (i, b)));
+            active_left.push(i);
+        }
+    }
+
+    pairs.sort_unstable();
+    pairs
+}
+
+fn near(a: &Aabb, b: &Aabb, margin_mm: i32) -> bool {
+    a.min_x < b.max_x + margin_mm
+        && a.max_x + margin_mm > b.min_x
+        && a.min_y < b.max_y + margin_mm
+        && a.max_y + margin_mm > b.min_y
 }
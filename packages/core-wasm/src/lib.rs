@@ -3,13 +3,27 @@ use wasm_bindgen::prelude::*;
 pub mod api;
 pub mod constraints;
 pub mod model;
+pub mod patch;
+pub mod plugins;
 pub mod geometry;
 
+pub use api::session::PlanSession;
+
 #[wasm_bindgen]
 pub fn validate_layout_json(kitchen_state_json: String) -> String {
     api::validate_layout::validate_layout_json(kitchen_state_json)
 }
 
+#[wasm_bindgen]
+pub fn validate_layout_with_rulesets_json(kitchen_state_json: String, rulesets_json: String) -> String {
+    api::validate_layout::validate_layout_with_rulesets_json(kitchen_state_json, rulesets_json)
+}
+
+#[wasm_bindgen]
+pub fn validate_drag_json(kitchen_state_json: String, object_ids_json: String) -> String {
+    api::validate_layout::validate_drag_json(kitchen_state_json, object_ids_json)
+}
+
 #[wasm_bindgen]
 pub fn derive_render_model_json(kitchen_state_json: String, quality: String) -> String {
     api::derive_render_model::derive_render_model_json(kitchen_state_json, quality)
@@ -29,3 +43,33 @@ pub fn normalize_state_json(kitchen_state_json: String) -> String {
 pub fn compute_room_metrics_json(kitchen_state_json: String) -> String {
     api::room_metrics::compute_room_metrics_json(kitchen_state_json)
 }
+
+#[wasm_bindgen]
+pub fn compute_proposal_metrics_json(kitchen_state_json: String) -> String {
+    api::proposal_metrics::compute_proposal_metrics_json(kitchen_state_json)
+}
+
+#[wasm_bindgen]
+pub fn apply_patch_with_inverse_json(kitchen_state_json: String, patch_json: String) -> String {
+    api::apply_patch::apply_patch_with_inverse_json(kitchen_state_json, patch_json)
+}
+
+#[wasm_bindgen]
+pub fn diff_state_json(before_json: String, after_json: String) -> String {
+    api::diff_state::diff_state_json(before_json, after_json)
+}
+
+#[wasm_bindgen]
+pub fn merge_patches_json(base_json: String, patch_a_json: String, patch_b_json: String) -> String {
+    api::merge_patches::merge_patches_json(base_json, patch_a_json, patch_b_json)
+}
+
+#[wasm_bindgen]
+pub fn apply_and_validate_json(kitchen_state_json: String, patch_json: String) -> String {
+    api::apply_and_validate::apply_and_validate_json(kitchen_state_json, patch_json)
+}
+
+#[wasm_bindgen]
+pub fn violation_delta_json(before_json: String, after_json: String) -> String {
+    api::violation_delta::violation_delta_json(before_json, after_json)
+}
@@ -23,6 +23,9 @@ pub struct ProjectMeta {
     pub units: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub ruleset_version: Option<String>,
+    /// Ids of constraints this project does not run.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disabled_constraints: Option<Vec<String>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -26,12 +26,25 @@ pub enum LayoutObjectKind {
     Module,
     Appliance,
     Decor,
+    /// Free-standing unit open on all sides.
+    Island,
+    /// Unit joined to a run or wall on one side and open on the others.
+    Peninsula,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Transform2Dmm {
     pub position_mm: Point2Mm,
     pub rotation_deg: i32,
+    /// Height of the object's underside above the floor; absent means it stands on the floor.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub elevation_mm: Option<i32>,
+}
+
+impl Transform2Dmm {
+    pub fn elevation(&self) -> i32 {
+        self.elevation_mm.unwrap_or(0)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
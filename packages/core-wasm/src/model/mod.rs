@@ -1,6 +1,8 @@
 pub mod kitchen_state;
 pub mod layout;
 pub mod patch;
+pub mod proposal_metrics;
 pub mod render_model;
 pub mod room;
+pub mod ruleset;
 pub mod violation;
@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+
+/// Scoring inputs for a layout proposal, matching the `proposal_metrics` contract.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProposalMetrics {
+    pub schema_version: String,
+    /// Perimeter of the sink–hob–fridge work triangle; 0 when one of them is missing.
+    pub triangle_proxy_mm: i32,
+    /// Sink–hob, hob–fridge and fridge–sink distances.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub triangle_edges_mm: Option<[i32; 3]>,
+    pub min_passage_mm: i32,
+    pub door_clearance_ok: bool,
+    pub utility_fit_score: f64,
+}
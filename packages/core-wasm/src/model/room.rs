@@ -38,8 +38,13 @@ pub enum OpeningKind {
 pub struct DoorSwing {
     pub direction: DoorSwingDirection,
     pub radius_mm: i32,
+    /// Whether the leaf swings into the room (the default) or away from it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub opens: Option<DoorOpens>,
 }
 
+/// Hinge side as seen from inside the room, facing the door. `Both` is a double door with a
+/// leaf hinged on each jamb.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum DoorSwingDirection {
@@ -48,6 +53,13 @@ pub enum DoorSwingDirection {
     Both,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum DoorOpens {
+    Inward,
+    Outward,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UtilityPoint {
     pub id: String,
@@ -61,7 +73,7 @@ pub struct UtilityPoint {
     pub zone_radius_mm: i32,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub enum UtilityKind {
     Water,
@@ -87,9 +99,21 @@ pub struct RestrictedZone {
     pub polygon_mm: Option<Vec<Point2Mm>>,
 }
 
+/// One wall of a room outline, running from `start_mm` to the next wall's start (the last
+/// wall closes back to the first). Opening and utility offsets are measured from `start_mm`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoomWall {
+    pub id: String,
+    pub start_mm: Point2Mm,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RoomModel {
     pub size_mm: SizeMm,
+    /// Wall polygon for non-rectangular rooms. Without it the room is the `size_mm` rectangle
+    /// with walls `south`, `north`, `west` and `east`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub outline_mm: Option<Vec<RoomWall>>,
     pub openings: Vec<Opening>,
     pub utilities: Vec<UtilityPoint>,
     pub restricted_zones: Vec<RestrictedZone>,
@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+
+/// Constraint parameters for one `ProjectMeta.ruleset_version`, so markets with different
+/// building norms can be validated against their own limits. Parameters added after the first
+/// rulesets default to the built-in values, so rulesets written before them still load.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Ruleset {
+    pub version: String,
+    pub min_passage_mm: i32,
+    pub min_wall_clearance_mm: i32,
+    pub default_door_swing_mm: i32,
+    /// Clear walkway needed on every open side of an island or peninsula.
+    #[serde(default = "default_island_walkway_mm")]
+    pub min_island_walkway_mm: i32,
+    /// Minimum distance between a gas point and any water or drain point.
+    #[serde(default = "default_gas_water_separation_mm")]
+    pub min_gas_water_separation_mm: i32,
+    #[serde(default = "default_gas_power_separation_mm")]
+    pub min_gas_power_separation_mm: i32,
+    #[serde(default = "default_water_power_separation_mm")]
+    pub min_water_power_separation_mm: i32,
+}
+
+fn default_island_walkway_mm() -> i32 {
+    1000
+}
+
+fn default_gas_water_separation_mm() -> i32 {
+    300
+}
+
+fn default_gas_power_separation_mm() -> i32 {
+    500
+}
+
+fn default_water_power_separation_mm() -> i32 {
+    600
+}
@@ -56,3 +56,11 @@ impl Violation {
         self
     }
 }
+
+/// Per code/severity count, matching the `violation_summary` entry of the proposal contract.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ViolationSummary {
+    pub code: String,
+    pub severity: Severity,
+    pub count: u32,
+}
@@ -0,0 +1,157 @@
+use crate::model::patch::{JsonPatchOp, PatchOp};
+use crate::patch::build_op;
+use serde_json::{Map, Value};
+use std::collections::{HashMap, HashSet};
+
+/// Produces RFC 6902 ops that turn `before` into `after`.
+///
+/// Arrays whose elements all carry a unique string `id` (layout objects, openings, utilities,
+/// restricted zones) are matched by id, so reorders become `move` ops and edits stay field-level.
+/// Other arrays are replaced wholesale when they differ.
+pub fn diff(before: &Value, after: &Value) -> Vec<JsonPatchOp> {
+    let mut ops = Vec::new();
+    diff_value(&mut Vec::new(), before, after, &mut ops);
+    ops
+}
+
+fn child(path: &[String], token: impl Into<String>) -> Vec<String> {
+    let mut next = path.to_vec();
+    next.push(token.into());
+    next
+}
+
+fn diff_value(path: &mut Vec<String>, before: &Value, after: &Value, ops: &mut Vec<JsonPatchOp>) {
+    if before == after {
+        return;
+    }
+    match (before, after) {
+        (Value::Object(b), Value::Object(a)) => diff_object(path, b, a, ops),
+        (Value::Array(b), Value::Array(a)) => match (element_ids(b), element_ids(a)) {
+            (Some(before_ids), Some(after_ids)) => diff_keyed_array(path, b, &before_ids, a, &after_ids, ops),
+            _ => ops.push(build_op(PatchOp::Replace, path, Some(after.clone()), None)),
+        },
+        _ => ops.push(build_op(PatchOp::Replace, path, Some(after.clone()), None)),
+    }
+}
+
+fn diff_object(path: &mut Vec<String>, before: &Map<String, Value>, after: &Map<String, Value>, ops: &mut Vec<JsonPatchOp>) {
+    for key in before.keys() {
+        if !after.contains_key(key) {
+            ops.push(build_op(PatchOp::Remove, &child(path, key.as_str()), None, None));
+        }
+    }
+    for (key, after_value) in after {
+        path.push(key.clone());
+        match before.get(key) {
+            Some(before_value) => diff_value(path, before_value, after_value, ops),
+            None => ops.push(build_op(PatchOp::Add, path, Some(after_value.clone()), None)),
+        }
+        path.pop();
+    }
+}
+
+fn element_ids(items: &[Value]) -> Option<Vec<&str>> {
+    let mut seen = HashSet::new();
+    let mut ids = Vec::with_capacity(items.len());
+    for item in items {
+        let id = item.get("id")?.as_str()?;
+        if !seen.insert(id) {
+            return None;
+        }
+        ids.push(id);
+    }
+    Some(ids)
+}
+
+/// Indices into `seq` forming a longest strictly increasing subsequence.
+fn longest_increasing_subsequence(seq: &[usize]) -> Vec<usize> {
+    let mut tails: Vec<usize> = Vec::new();
+    let mut prev: Vec<Option<usize>> = vec![None; seq.len()];
+    for (i, value) in seq.iter().enumerate() {
+        let pos = tails.partition_point(|&t| seq[t] < *value);
+        if pos > 0 {
+            prev[i] = Some(tails[pos - 1]);
+        }
+        if pos == tails.len() {
+            tails.push(i);
+        } else {
+            tails[pos] = i;
+        }
+    }
+
+    let mut result = Vec::with_capacity(tails.len());
+    let mut cursor = tails.last().copied();
+    while let Some(i) = cursor {
+        result.push(i);
+        cursor = prev[i];
+    }
+    result.reverse();
+    result
+}
+
+fn diff_keyed_array(
+    path: &mut Vec<String>,
+    before: &[Value],
+    before_ids: &[&str],
+    after: &[Value],
+    after_ids: &[&str],
+    ops: &mut Vec<JsonPatchOp>,
+) {
+    let after_index: HashMap<&str, usize> = after_ids.iter().enumerate().map(|(i, id)| (*id, i)).collect();
+    let before_by_id: HashMap<&str, &Value> = before_ids.iter().copied().zip(before.iter()).collect();
+
+    let mut working: Vec<&str> = before_ids.to_vec();
+    for i in (0..working.len()).rev() {
+        if !after_index.contains_key(working[i]) {
+            ops.push(build_op(PatchOp::Remove, &child(path, i.to_string()), None, None));
+            working.remove(i);
+        }
+    }
+
+    // Elements on the longest run already in target order stay put; everything else is moved
+    // (or added) directly behind its predecessor in `after`.
+    let order: Vec<usize> = working.iter().map(|id| after_index[id]).collect();
+    let stable: HashSet<&str> = longest_increasing_subsequence(&order)
+        .into_iter()
+        .map(|i| working[i])
+        .collect();
+
+    for (j, id) in after_ids.iter().enumerate() {
+        if stable.contains(id) {
+            continue;
+        }
+        let current = working.iter().position(|w| w == id);
+        if let Some(k) = current {
+            working.remove(k);
+        }
+        let target = match j {
+            0 => 0,
+            _ => working.iter().position(|w| *w == after_ids[j - 1]).map_or(0, |p| p + 1),
+        };
+        match current {
+            Some(k) if k == target => {}
+            Some(k) => ops.push(build_op(
+                PatchOp::Move,
+                &child(path, target.to_string()),
+                None,
+                Some(&child(path, k.to_string())),
+            )),
+            None => ops.push(build_op(
+                PatchOp::Add,
+                &child(path, target.to_string()),
+                Some(after[j].clone()),
+                None,
+            )),
+        }
+        working.insert(target, id);
+    }
+    debug_assert_eq!(working, after_ids);
+
+    for (j, id) in after_ids.iter().enumerate() {
+        if let Some(before_value) = before_by_id.get(id) {
+            path.push(j.to_string());
+            diff_value(path, before_value, &after[j], ops);
+            path.pop();
+        }
+    }
+}
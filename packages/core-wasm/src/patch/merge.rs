@@ -0,0 +1,240 @@
+use crate::model::patch::ProposedPatch;
+use crate::model::violation::Violation;
+use crate::patch::diff::diff;
+use crate::patch::{apply_patch, pointer};
+use serde_json::{Map, Value};
+use std::collections::{HashMap, HashSet};
+
+#[derive(Debug, Clone)]
+pub struct MergeOutcome {
+    /// State after applying `a` and the non-conflicting part of `b`.
+    pub merged: Value,
+    /// `b` rebased onto the result of `a`; applying it to that state yields `merged`.
+    pub rebased: ProposedPatch,
+    pub conflicts: Vec<Violation>,
+}
+
+struct Conflict<'a> {
+    path: Vec<String>,
+    owner: Option<&'a str>,
+    kind: &'static str,
+    base: Option<&'a Value>,
+    ours: Option<&'a Value>,
+    theirs: Option<&'a Value>,
+}
+
+impl Conflict<'_> {
+    fn to_violation(&self) -> Violation {
+        let message = match self.kind {
+            "delete_modify" => "patches delete and modify the same value",
+            _ => "patches change the same value",
+        };
+        let mut details = HashMap::new();
+        details.insert("path".to_string(), Value::String(pointer::format_pointer(&self.path)));
+        details.insert("kind".to_string(), Value::String(self.kind.to_string()));
+        details.insert("base".to_string(), self.base.cloned().unwrap_or(Value::Null));
+        details.insert("ours".to_string(), self.ours.cloned().unwrap_or(Value::Null));
+        details.insert("theirs".to_string(), self.theirs.cloned().unwrap_or(Value::Null));
+        let object_ids = self.owner.map(|id| vec![id.to_string()]).unwrap_or_default();
+        Violation::error("patch.conflict", message, object_ids).with_details(details)
+    }
+}
+
+/// Rebases patch `b` onto patch `a`, both written against `base`.
+///
+/// Edits are compared per field, with id-carrying arrays matched by `id`. Disjoint edits merge;
+/// when both patches change the same field differently, or one deletes what the other modifies,
+/// `a` wins and the clash is reported as a `patch.conflict` violation.
+pub fn merge(base: &Value, a: &ProposedPatch, b: &ProposedPatch) -> Result<MergeOutcome, Vec<Violation>> {
+    let (ours, _) = apply_patch(base, a).map_err(|violations| tag_patch(violations, "a"))?;
+    let (theirs, _) = apply_patch(base, b).map_err(|violations| tag_patch(violations, "b"))?;
+
+    let mut conflicts = Vec::new();
+    let merged = merge_value(&mut Vec::new(), None, Some(base), Some(&ours), Some(&theirs), &mut conflicts)
+        .unwrap_or(Value::Null);
+    let rebased = ProposedPatch {
+        ops: diff(&ours, &merged),
+        reason: b.reason.clone(),
+        source: b.source.clone(),
+    };
+
+    Ok(MergeOutcome {
+        rebased,
+        conflicts: conflicts.iter().map(Conflict::to_violation).collect(),
+        merged,
+    })
+}
+
+fn tag_patch(mut violations: Vec<Violation>, patch: &str) -> Vec<Violation> {
+    for violation in &mut violations {
+        violation
+            .details
+            .get_or_insert_with(HashMap::new)
+            .insert("patch".to_string(), Value::String(patch.to_string()));
+    }
+    violations
+}
+
+fn merge_value<'a>(
+    path: &mut Vec<String>,
+    owner: Option<&'a str>,
+    base: Option<&'a Value>,
+    ours: Option<&'a Value>,
+    theirs: Option<&'a Value>,
+    conflicts: &mut Vec<Conflict<'a>>,
+) -> Option<Value> {
+    if ours == theirs || theirs == base {
+        return ours.cloned();
+    }
+    if ours == base {
+        return theirs.cloned();
+    }
+
+    match (ours, theirs) {
+        (Some(Value::Object(o)), Some(Value::Object(t))) => {
+            let b = base.and_then(Value::as_object);
+            return Some(Value::Object(merge_object(path, owner, b, o, t, conflicts)));
+        }
+        (Some(Value::Array(o)), Some(Value::Array(t))) => {
+            let b = match base {
+                Some(Value::Array(items)) => Some(items.as_slice()),
+                _ => None,
+            };
+            if let Some(merged) = merge_keyed_array(path, b, o, t, conflicts) {
+                return Some(Value::Array(merged));
+            }
+        }
+        _ => {}
+    }
+
+    conflicts.push(Conflict {
+        path: path.clone(),
+        owner,
+        kind: if ours.is_none() || theirs.is_none() { "delete_modify" } else { "field" },
+        base,
+        ours,
+        theirs,
+    });
+    ours.cloned()
+}
+
+fn merge_object<'a>(
+    path: &mut Vec<String>,
+    owner: Option<&'a str>,
+    base: Option<&'a Map<String, Value>>,
+    ours: &'a Map<String, Value>,
+    theirs: &'a Map<String, Value>,
+    conflicts: &mut Vec<Conflict<'a>>,
+) -> Map<String, Value> {
+    let mut keys: Vec<&String> = ours.keys().chain(theirs.keys()).collect();
+    if let Some(base) = base {
+        keys.extend(base.keys());
+    }
+    keys.sort();
+    keys.dedup();
+
+    let mut merged = Map::new();
+    for key in keys {
+        path.push(key.clone());
+        let value = merge_value(
+            path,
+            owner,
+            base.and_then(|m| m.get(key)),
+            ours.get(key),
+            theirs.get(key),
+            conflicts,
+        );
+        path.pop();
+        if let Some(value) = value {
+            merged.insert(key.clone(), value);
+        }
+    }
+    merged
+}
+
+fn keyed(items: &[Value]) -> Option<(Vec<&str>, HashMap<&str, &Value>)> {
+    let mut order = Vec::with_capacity(items.len());
+    let mut by_id = HashMap::with_capacity(items.len());
+    for item in items {
+        let id = item.get("id")?.as_str()?;
+        if by_id.insert(id, item).is_some() {
+            return None;
+        }
+        order.push(id);
+    }
+    Some((order, by_id))
+}
+
+fn reordered(base: &[&str], side: &[&str]) -> bool {
+    let side_set: HashSet<&str> = side.iter().copied().collect();
+    let base_set: HashSet<&str> = base.iter().copied().collect();
+    let kept_base = base.iter().filter(|id| side_set.contains(*id));
+    let kept_side = side.iter().filter(|id| base_set.contains(*id));
+    !kept_base.eq(kept_side)
+}
+
+/// Merges arrays element-by-element on `id`. Returns `None` when either side is not id-keyed,
+/// in which case the caller treats the arrays as opaque values.
+fn merge_keyed_array<'a>(
+    path: &mut Vec<String>,
+    base: Option<&'a [Value]>,
+    ours: &'a [Value],
+    theirs: &'a [Value],
+    conflicts: &mut Vec<Conflict<'a>>,
+) -> Option<Vec<Value>> {
+    let (base_order, base_by_id) = keyed(base.unwrap_or(&[]))?;
+    let (ours_order, ours_by_id) = keyed(ours)?;
+    let (theirs_order, theirs_by_id) = keyed(theirs)?;
+
+    // Follow whichever side reordered the surviving elements (ours if both did), then slot
+    // elements only the other side knows about behind their predecessor.
+    // Where both sides add elements at the same spot, ours come first.
+    let follow_theirs = reordered(&base_order, &theirs_order) && !reordered(&base_order, &ours_order);
+    let (primary, secondary) = if follow_theirs {
+        (&theirs_order, &ours_order)
+    } else {
+        (&ours_order, &theirs_order)
+    };
+    let mut order: Vec<&str> = primary.clone();
+    let mut cursor: Option<usize> = None;
+    for id in secondary {
+        match order.iter().position(|o| o == id) {
+            Some(pos) => cursor = Some(pos),
+            None => {
+                let mut pos = cursor.map_or(0, |c| c + 1);
+                while !follow_theirs
+                    && pos < order.len()
+                    && !base_by_id.contains_key(order[pos])
+                    && !theirs_by_id.contains_key(order[pos])
+                {
+                    pos += 1;
+                }
+                order.insert(pos, id);
+                cursor = Some(pos);
+            }
+        }
+    }
+    for id in &base_order {
+        if !order.contains(id) {
+            order.push(id);
+        }
+    }
+
+    let mut merged = Vec::with_capacity(order.len());
+    for id in order {
+        path.push(format!("[id={}]", id));
+        let value = merge_value(
+            path,
+            Some(id),
+            base_by_id.get(id).copied(),
+            ours_by_id.get(id).copied(),
+            theirs_by_id.get(id).copied(),
+            conflicts,
+        );
+        path.pop();
+        if let Some(value) = value {
+            merged.push(value);
+        }
+    }
+    Some(merged)
+}
@@ -0,0 +1,221 @@
+pub mod diff;
+pub mod merge;
+pub mod pointer;
+
+use crate::model::patch::{JsonPatchOp, PatchOp, ProposedPatch};
+use crate::model::violation::Violation;
+use serde_json::Value;
+use std::collections::HashMap;
+
+#[derive(Debug, Clone)]
+pub struct PatchError {
+    pub code: &'static str,
+    pub message: &'static str,
+    pub reason: String,
+}
+
+impl PatchError {
+    fn new(code: &'static str, message: &'static str, reason: &str) -> Self {
+        Self {
+            code,
+            message,
+            reason: reason.to_string(),
+        }
+    }
+
+    pub fn invalid_pointer(reason: &str) -> Self {
+        Self::new("patch.invalid_pointer", "invalid patch path", reason)
+    }
+
+    pub fn path_not_found(reason: &str) -> Self {
+        Self::new("patch.path_not_found", "patch path does not exist", reason)
+    }
+
+    pub fn index_out_of_bounds() -> Self {
+        Self::new(
+            "patch.index_out_of_bounds",
+            "patch array index out of bounds",
+            "pointer_index_out_of_bounds",
+        )
+    }
+
+    pub fn missing_value(op: &str) -> Self {
+        Self::new(
+            "patch.missing_value",
+            "operation requires value",
+            &format!("{}_requires_value", op),
+        )
+    }
+
+    pub fn missing_from(op: &str) -> Self {
+        Self::new(
+            "patch.missing_from",
+            "operation requires from",
+            &format!("{}_requires_from", op),
+        )
+    }
+
+    pub fn invalid_move() -> Self {
+        Self::new(
+            "patch.invalid_move",
+            "cannot move a value into one of its children",
+            "from_is_prefix_of_path",
+        )
+    }
+
+    pub fn test_failed() -> Self {
+        Self::new("patch.test_failed", "test operation failed", "value_mismatch")
+    }
+
+    /// Describes the failure of the op at `op_index`, so callers can target the exact op.
+    pub fn to_violation(&self, op_index: usize, op: &JsonPatchOp) -> Violation {
+        let mut details = HashMap::new();
+        details.insert("op_index".to_string(), Value::from(op_index));
+        details.insert("op".to_string(), Value::String(op_name(&op.op).to_string()));
+        details.insert("path".to_string(), Value::String(op.path.clone()));
+        if let Some(from) = &op.from {
+            details.insert("from".to_string(), Value::String(from.clone()));
+        }
+        details.insert("reason".to_string(), Value::String(self.reason.clone()));
+        Violation::error(self.code, self.message, vec![]).with_details(details)
+    }
+}
+
+fn op_name(op: &PatchOp) -> &'static str {
+    match op {
+        PatchOp::Add => "add",
+        PatchOp::Remove => "remove",
+        PatchOp::Replace => "replace",
+        PatchOp::Move => "move",
+        PatchOp::Copy => "copy",
+        PatchOp::Test => "test",
+    }
+}
+
+fn required_value(op: &JsonPatchOp) -> Result<Value, PatchError> {
+    op.value
+        .clone()
+        .ok_or_else(|| PatchError::missing_value(op_name(&op.op)))
+}
+
+fn required_from(doc: &Value, op: &JsonPatchOp) -> Result<Vec<String>, PatchError> {
+    let from = op
+        .from
+        .as_deref()
+        .ok_or_else(|| PatchError::missing_from(op_name(&op.op)))?;
+    let tokens = pointer::parse_pointer(from)?;
+    pointer::resolve_selectors(doc, &tokens)
+}
+
+pub(crate) fn build_op(op: PatchOp, path: &[String], value: Option<Value>, from: Option<&[String]>) -> JsonPatchOp {
+    JsonPatchOp {
+        op,
+        path: pointer::format_pointer(path),
+        value,
+        from: from.map(pointer::format_pointer),
+    }
+}
+
+/// Undoes an insert at `resolved`: restores the overwritten value or removes the new one.
+fn undo_add(resolved: &[String], previous: Option<Value>) -> JsonPatchOp {
+    match previous {
+        Some(value) => build_op(PatchOp::Replace, resolved, Some(value), None),
+        None => build_op(PatchOp::Remove, resolved, None, None),
+    }
+}
+
+/// Applies a single RFC 6902 operation to `doc` in place and returns the ops that undo it,
+/// in the order they must be applied. Paths may use `[id=...]` array selectors; the undo ops
+/// always carry the concrete indices they were resolved to.
+pub fn apply_op(doc: &mut Value, op: &JsonPatchOp) -> Result<Vec<JsonPatchOp>, PatchError> {
+    let raw_path = pointer::parse_pointer(&op.path)?;
+    let path = pointer::resolve_selectors(doc, &raw_path)?;
+    match op.op {
+        PatchOp::Add => {
+            let (resolved, previous) = pointer::add(doc, &path, required_value(op)?)?;
+            Ok(vec![undo_add(&resolved, previous)])
+        }
+        PatchOp::Remove => {
+            let removed = pointer::remove(doc, &path)?;
+            Ok(vec![build_op(PatchOp::Add, &path, Some(removed), None)])
+        }
+        PatchOp::Replace => {
+            let previous = pointer::replace(doc, &path, required_value(op)?)?;
+            Ok(vec![build_op(PatchOp::Replace, &path, Some(previous), None)])
+        }
+        PatchOp::Move => {
+            let from = required_from(doc, op)?;
+            if from == path {
+                return pointer::get(doc, &from).map(|_| Vec::new());
+            }
+            if path.len() > from.len() && path.starts_with(&from) {
+                return Err(PatchError::invalid_move());
+            }
+            let value = pointer::remove(doc, &from)?;
+            // Selectors in the target are resolved against the document after removal, the
+            // same way RFC 6902 interprets a numeric target index.
+            let (resolved, previous) = match pointer::resolve_selectors(doc, &raw_path)
+                .and_then(|target| pointer::add(doc, &target, value.clone()))
+            {
+                Ok(outcome) => outcome,
+                Err(err) => {
+                    let _ = pointer::add(doc, &from, value);
+                    return Err(err);
+                }
+            };
+            let mut inverse = vec![build_op(PatchOp::Move, &from, None, Some(&resolved))];
+            if let Some(overwritten) = previous {
+                inverse.push(build_op(PatchOp::Add, &resolved, Some(overwritten), None));
+            }
+            Ok(inverse)
+        }
+        PatchOp::Copy => {
+            let from = required_from(doc, op)?;
+            let value = pointer::get(doc, &from)?.clone();
+            let (resolved, previous) = pointer::add(doc, &path, value)?;
+            Ok(vec![undo_add(&resolved, previous)])
+        }
+        PatchOp::Test => {
+            let expected = required_value(op)?;
+            if *pointer::get(doc, &path)? != expected {
+                return Err(PatchError::test_failed());
+            }
+            Ok(Vec::new())
+        }
+    }
+}
+
+/// Applies every op of `patch` to a copy of `doc`. Either all ops succeed or `doc` is left as-is
+/// and the violation names the first failing op. On success the returned patch restores `doc`
+/// when applied to the new value.
+pub fn apply_patch(doc: &Value, patch: &ProposedPatch) -> Result<(Value, ProposedPatch), Vec<Violation>> {
+    let mut working = doc.clone();
+    let inverse = apply_patch_in_place(&mut working, patch)?;
+    Ok((working, inverse))
+}
+
+/// Same as [`apply_patch`] but edits `doc` directly, undoing the ops already applied when a
+/// later one fails.
+pub fn apply_patch_in_place(doc: &mut Value, patch: &ProposedPatch) -> Result<ProposedPatch, Vec<Violation>> {
+    let mut undo_steps: Vec<Vec<JsonPatchOp>> = Vec::with_capacity(patch.ops.len());
+    for (index, op) in patch.ops.iter().enumerate() {
+        match apply_op(doc, op) {
+            Ok(undo) => undo_steps.push(undo),
+            Err(err) => {
+                for undo in undo_steps.iter().rev().flatten() {
+                    let _ = apply_op(doc, undo);
+                }
+                return Err(vec![err.to_violation(index, op)]);
+            }
+        }
+    }
+
+    Ok(ProposedPatch {
+        ops: undo_steps.into_iter().rev().flatten().collect(),
+        reason: Some(match &patch.reason {
+            Some(reason) => format!("undo: {}", reason),
+            None => "undo".to_string(),
+        }),
+        source: patch.source.clone(),
+    })
+}
@@ -0,0 +1,160 @@
+use crate::patch::PatchError;
+use serde_json::Value;
+
+fn decode_token(token: &str) -> String {
+    token.replace("~1", "/").replace("~0", "~")
+}
+
+pub fn encode_token(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
+
+pub fn parse_pointer(pointer: &str) -> Result<Vec<String>, PatchError> {
+    if pointer.is_empty() {
+        return Ok(Vec::new());
+    }
+    let Some(rest) = pointer.strip_prefix('/') else {
+        return Err(PatchError::invalid_pointer("pointer_missing_leading_slash"));
+    };
+    Ok(rest.split('/').map(decode_token).collect())
+}
+
+pub fn format_pointer(tokens: &[String]) -> String {
+    tokens.iter().map(|t| format!("/{}", encode_token(t))).collect()
+}
+
+fn parse_index(token: &str) -> Result<usize, String> {
+    let valid = !token.is_empty()
+        && token.chars().all(|c| c.is_ascii_digit())
+        && (token == "0" || !token.starts_with('0'));
+    if !valid {
+        return Err("pointer_index_invalid".to_string());
+    }
+    token.parse().map_err(|_| "pointer_index_invalid".to_string())
+}
+
+/// Returns the id referenced by an `[id=...]` array selector token.
+fn id_selector(token: &str) -> Option<&str> {
+    token.strip_prefix("[id=")?.strip_suffix(']')
+}
+
+fn find_by_id(arr: &[Value], id: &str) -> Option<usize> {
+    arr.iter()
+        .position(|item| item.get("id").and_then(Value::as_str) == Some(id))
+}
+
+/// Rewrites `[id=...]` selectors into concrete array indices against the current `doc`, so
+/// `/layout/objects/[id=obj_sink]/transform_mm` addresses the object regardless of its position.
+/// Resolution stops at the first segment that does not exist; the caller reports that error.
+pub fn resolve_selectors(doc: &Value, tokens: &[String]) -> Result<Vec<String>, PatchError> {
+    let mut resolved = tokens.to_vec();
+    let mut current = Some(doc);
+    for token in resolved.iter_mut() {
+        let Some(node) = current else {
+            break;
+        };
+        if let (Value::Array(arr), Some(id)) = (node, id_selector(token)) {
+            let idx = find_by_id(arr, id).ok_or_else(|| PatchError::path_not_found("pointer_id_not_found"))?;
+            *token = idx.to_string();
+        }
+        current = match node {
+            Value::Object(map) => map.get(token.as_str()),
+            Value::Array(arr) => parse_index(token).ok().and_then(|idx| arr.get(idx)),
+            _ => None,
+        };
+    }
+    Ok(resolved)
+}
+
+pub fn get<'a>(doc: &'a Value, tokens: &[String]) -> Result<&'a Value, PatchError> {
+    let mut current = doc;
+    for token in tokens {
+        current = match current {
+            Value::Object(map) => map
+                .get(token)
+                .ok_or_else(|| PatchError::path_not_found("pointer_not_found"))?,
+            Value::Array(arr) => {
+                let idx = parse_index(token).map_err(|reason| PatchError::invalid_pointer(&reason))?;
+                arr.get(idx)
+                    .ok_or_else(PatchError::index_out_of_bounds)?
+            }
+            _ => return Err(PatchError::path_not_found("pointer_target_invalid")),
+        };
+    }
+    Ok(current)
+}
+
+fn get_mut<'a>(doc: &'a mut Value, tokens: &[String]) -> Result<&'a mut Value, PatchError> {
+    let mut current = doc;
+    for token in tokens {
+        current = match current {
+            Value::Object(map) => map
+                .get_mut(token)
+                .ok_or_else(|| PatchError::path_not_found("pointer_not_found"))?,
+            Value::Array(arr) => {
+                let idx = parse_index(token).map_err(|reason| PatchError::invalid_pointer(&reason))?;
+                arr.get_mut(idx)
+                    .ok_or_else(PatchError::index_out_of_bounds)?
+            }
+            _ => return Err(PatchError::path_not_found("pointer_target_invalid")),
+        };
+    }
+    Ok(current)
+}
+
+/// Inserts `value` at `tokens`. Arrays shift on insert and accept `-` as the append index.
+/// Returns the resolved pointer (with `-` replaced by the concrete index) and any value that
+/// was overwritten, which together are enough to undo the insert.
+pub fn add(doc: &mut Value, tokens: &[String], value: Value) -> Result<(Vec<String>, Option<Value>), PatchError> {
+    let Some((last, parent_tokens)) = tokens.split_last() else {
+        let previous = std::mem::replace(doc, value);
+        return Ok((Vec::new(), Some(previous)));
+    };
+    match get_mut(doc, parent_tokens)? {
+        Value::Object(map) => {
+            let previous = map.insert(last.clone(), value);
+            Ok((tokens.to_vec(), previous))
+        }
+        Value::Array(arr) => {
+            let idx = if last == "-" {
+                arr.len()
+            } else {
+                parse_index(last).map_err(|reason| PatchError::invalid_pointer(&reason))?
+            };
+            if idx > arr.len() {
+                return Err(PatchError::index_out_of_bounds());
+            }
+            arr.insert(idx, value);
+            let mut resolved = parent_tokens.to_vec();
+            resolved.push(idx.to_string());
+            Ok((resolved, None))
+        }
+        _ => Err(PatchError::path_not_found("pointer_target_invalid")),
+    }
+}
+
+/// Removes and returns the value at `tokens`, shifting later array elements down.
+pub fn remove(doc: &mut Value, tokens: &[String]) -> Result<Value, PatchError> {
+    let Some((last, parent_tokens)) = tokens.split_last() else {
+        return Err(PatchError::invalid_pointer("pointer_root_not_removable"));
+    };
+    match get_mut(doc, parent_tokens)? {
+        Value::Object(map) => map
+            .remove(last)
+            .ok_or_else(|| PatchError::path_not_found("pointer_not_found")),
+        Value::Array(arr) => {
+            let idx = parse_index(last).map_err(|reason| PatchError::invalid_pointer(&reason))?;
+            if idx >= arr.len() {
+                return Err(PatchError::index_out_of_bounds());
+            }
+            Ok(arr.remove(idx))
+        }
+        _ => Err(PatchError::path_not_found("pointer_target_invalid")),
+    }
+}
+
+/// Replaces an existing value at `tokens` and returns the previous one.
+pub fn replace(doc: &mut Value, tokens: &[String], value: Value) -> Result<Value, PatchError> {
+    let target = get_mut(doc, tokens)?;
+    Ok(std::mem::replace(target, value))
+}
@@ -1 +1,44 @@
-pub struct PluginRegistry;
+use crate::constraints::{register_builtin, Constraint};
+use crate::model::kitchen_state::ProjectMeta;
+
+/// Ordered set of constraints a validation pass runs. Native rule packs add their rules with
+/// `register` next to the built-in ones.
+#[derive(Default)]
+pub struct PluginRegistry {
+    constraints: Vec<Box<dyn Constraint>>,
+}
+
+impl PluginRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registry holding every rule that ships with the core.
+    pub fn with_builtin() -> Self {
+        let mut registry = Self::new();
+        register_builtin(&mut registry);
+        registry
+    }
+
+    /// Adds `constraint`, replacing a registered one with the same id in place.
+    pub fn register(&mut self, constraint: Box<dyn Constraint>) {
+        match self.constraints.iter().position(|c| c.id() == constraint.id()) {
+            Some(index) => self.constraints[index] = constraint,
+            None => self.constraints.push(constraint),
+        }
+    }
+
+    pub fn get(&self, id: &str) -> Option<&dyn Constraint> {
+        self.constraints.iter().find(|c| c.id() == id).map(|c| c.as_ref())
+    }
+
+    pub fn constraints(&self) -> impl Iterator<Item = &dyn Constraint> {
+        self.constraints.iter().map(|c| c.as_ref())
+    }
+
+    /// Registered constraints minus those listed in `project.disabled_constraints`.
+    pub fn enabled_for<'a>(&'a self, project: &'a ProjectMeta) -> impl Iterator<Item = &'a dyn Constraint> {
+        let disabled = project.disabled_constraints.as_deref().unwrap_or_default();
+        self.constraints().filter(move |c| !disabled.iter().any(|id| id == c.id()))
+    }
+}
@@ -0,0 +1,63 @@
+use planforge_core_wasm::apply_and_validate_json;
+
+fn state() -> serde_json::Value {
+    serde_json::json!({
+        "schema_version": "0.1.0",
+        "project": { "project_id": "p1", "revision_id": "r1", "units": "mm" },
+        "room": {
+            "size_mm": { "width": 3200, "depth": 2600, "height": 2700 },
+            "openings": [],
+            "utilities": [],
+            "restricted_zones": []
+        },
+        "layout": {
+            "objects": [
+                {
+                    "id": "obj_a",
+                    "kind": "module",
+                    "catalog_item_id": "base_sink_600",
+                    "transform_mm": { "position_mm": { "x": 0, "y": 0 }, "rotation_deg": 0 },
+                    "dims_mm": { "width": 600, "depth": 600, "height": 720 },
+                    "material_slots": {}
+                },
+                {
+                    "id": "obj_b",
+                    "kind": "module",
+                    "catalog_item_id": "base_drawers_800",
+                    "transform_mm": { "position_mm": { "x": 300, "y": 0 }, "rotation_deg": 0 },
+                    "dims_mm": { "width": 600, "depth": 600, "height": 720 },
+                    "material_slots": {}
+                }
+            ]
+        },
+        "catalog_refs": { "modules_catalog_version": "modules_demo_0.1.0", "materials_catalog_version": "materials_demo_0.1.0" }
+    })
+}
+
+fn codes(list: &serde_json::Value) -> Vec<&str> {
+    list.as_array().unwrap().iter().map(|v| v["code"].as_str().unwrap()).collect()
+}
+
+#[test]
+fn reports_patched_state_violations_and_delta() {
+    let patch = serde_json::json!({
+        "ops": [{ "op": "replace", "path": "/layout/objects/[id=obj_b]/transform_mm/position_mm/x", "value": 3000 }]
+    });
+    let response = apply_and_validate_json(state().to_string(), patch.to_string());
+    let value: serde_json::Value = serde_json::from_str(&response).unwrap();
+
+    assert_eq!(value["kitchen_state"]["layout"]["objects"][1]["transform_mm"]["position_mm"]["x"], 3000);
+    assert_eq!(codes(&value["violations"]), vec!["layout.out_of_bounds", "layout.wall_clearance"]);
+    assert_eq!(codes(&value["delta"]["introduced"]), vec!["layout.out_of_bounds", "layout.wall_clearance"]);
+    assert_eq!(codes(&value["delta"]["resolved"]), vec!["layout.collision"]);
+}
+
+#[test]
+fn patch_failure_returns_original_state() {
+    let patch = serde_json::json!({ "ops": [{ "op": "remove", "path": "/layout/objects/7" }] });
+    let response = apply_and_validate_json(state().to_string(), patch.to_string());
+    let value: serde_json::Value = serde_json::from_str(&response).unwrap();
+    assert_eq!(value["violations"][0]["code"], "patch.index_out_of_bounds");
+    assert_eq!(value["kitchen_state"], state());
+    assert!(value.get("delta").is_none());
+}
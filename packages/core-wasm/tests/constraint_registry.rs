@@ -0,0 +1,140 @@
+use planforge_core_wasm::constraints::ruleset::RulesetCatalog;
+use planforge_core_wasm::constraints::{
+    validate_constraints_with, Constraint, ConstraintCategory, ConstraintContext, ValidationScope,
+};
+use planforge_core_wasm::model::kitchen_state::KitchenState;
+use planforge_core_wasm::model::violation::{Severity, Violation};
+use planforge_core_wasm::plugins::registry::PluginRegistry;
+
+/// Two base units overlapping by 100 mm.
+fn state(disabled: Option<&[&str]>) -> KitchenState {
+    let mut state = serde_json::json!({
+        "schema_version": "0.1.0",
+        "project": { "project_id": "p1", "revision_id": "r1", "units": "mm" },
+        "room": {
+            "size_mm": { "width": 3200, "depth": 2600, "height": 2700 },
+            "openings": [],
+            "utilities": [],
+            "restricted_zones": []
+        },
+        "layout": {
+            "objects": [
+                {
+                    "id": "obj_a",
+                    "kind": "module",
+                    "catalog_item_id": "base_600",
+                    "transform_mm": { "position_mm": { "x": 100, "y": 100 }, "rotation_deg": 0 },
+                    "dims_mm": { "width": 600, "depth": 600, "height": 720 },
+                    "material_slots": {}
+                },
+                {
+                    "id": "obj_b",
+                    "kind": "module",
+                    "catalog_item_id": "base_600",
+                    "transform_mm": { "position_mm": { "x": 600, "y": 100 }, "rotation_deg": 0 },
+                    "dims_mm": { "width": 600, "depth": 600, "height": 720 },
+                    "material_slots": {}
+                }
+            ]
+        },
+        "catalog_refs": { "modules_catalog_version": "modules_demo_0.1.0", "materials_catalog_version": "materials_demo_0.1.0" }
+    });
+    if let Some(ids) = disabled {
+        state["project"]["disabled_constraints"] = serde_json::json!(ids);
+    }
+    serde_json::from_value(state).unwrap()
+}
+
+fn codes(violations: &[Violation]) -> Vec<&str> {
+    violations.iter().map(|v| v.code.as_str()).collect()
+}
+
+fn run(state: &KitchenState, registry: &PluginRegistry) -> Vec<Violation> {
+    validate_constraints_with(state, &ValidationScope::All, &RulesetCatalog::builtin(), registry)
+}
+
+/// Flags every object wider than 500 mm.
+struct MaxWidth;
+
+impl Constraint for MaxWidth {
+    fn id(&self) -> &'static str {
+        "max_width"
+    }
+
+    fn category(&self) -> ConstraintCategory {
+        ConstraintCategory::Clearance
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn evaluate(&self, ctx: &ConstraintContext, violations: &mut Vec<Violation>) {
+        for fp in ctx.footprints.iter().filter(|fp| ctx.scope.includes(&fp.id) && fp.width > 500) {
+            violations.push(self.violation("custom.max_width", "object is too wide", vec![fp.id.clone()]));
+        }
+    }
+}
+
+/// Replaces the built-in collision rule with one that never fires.
+struct NoCollisions;
+
+impl Constraint for NoCollisions {
+    fn id(&self) -> &'static str {
+        "collision"
+    }
+
+    fn category(&self) -> ConstraintCategory {
+        ConstraintCategory::Collision
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn evaluate(&self, _ctx: &ConstraintContext, _violations: &mut Vec<Violation>) {}
+}
+
+#[test]
+fn builtin_rules_have_unique_ids() {
+    let registry = PluginRegistry::with_builtin();
+    let mut ids: Vec<&str> = registry.constraints().map(|c| c.id()).collect();
+    let count = ids.len();
+    ids.sort();
+    ids.dedup();
+    assert_eq!(ids.len(), count);
+    assert!(registry.get("collision").is_some());
+}
+
+#[test]
+fn project_can_disable_a_rule() {
+    let registry = PluginRegistry::with_builtin();
+    assert!(codes(&run(&state(None), &registry)).contains(&"layout.collision"));
+
+    let violations = run(&state(Some(&["collision"])), &registry);
+    assert!(!codes(&violations).contains(&"layout.collision"));
+}
+
+#[test]
+fn registered_rule_runs_with_default_severity() {
+    let mut registry = PluginRegistry::with_builtin();
+    registry.register(Box::new(MaxWidth));
+
+    let violations = run(&state(None), &registry);
+    let custom: Vec<&Violation> = violations.iter().filter(|v| v.code == "custom.max_width").collect();
+    assert_eq!(custom.len(), 2);
+    assert!(custom.iter().all(|v| matches!(v.severity, Severity::Warning)));
+
+    let violations = run(&state(Some(&["max_width"])), &registry);
+    assert!(!codes(&violations).contains(&"custom.max_width"));
+}
+
+#[test]
+fn registering_an_existing_id_replaces_the_rule() {
+    let mut registry = PluginRegistry::with_builtin();
+    let count = registry.constraints().count();
+    registry.register(Box::new(NoCollisions));
+
+    assert_eq!(registry.constraints().count(), count);
+    assert!(!codes(&run(&state(None), &registry)).contains(&"layout.collision"));
+}
@@ -139,3 +139,672 @@ fn detects_restricted_zone() {
     let violations = value.get("violations").and_then(|v| v.as_array()).unwrap();
     assert!(violations.iter().any(|v| v.get("code").unwrap() == "layout.restricted_zone"));
 }
+
+#[test]
+fn rotated_corner_unit_uses_its_true_footprint() {
+    let mut state = base_state();
+    let corner_unit = |x: i32| {
+        serde_json::json!({
+            "id": "obj_corner",
+            "kind": "module",
+            "catalog_item_id": "base_corner_600",
+            "transform_mm": { "position_mm": { "x": x, "y": 1000 }, "rotation_deg": 45 },
+            "dims_mm": { "width": 600, "depth": 600, "height": 720 },
+            "material_slots": {}
+        })
+    };
+    let neighbour = serde_json::json!({
+        "id": "obj_b",
+        "kind": "module",
+        "catalog_item_id": "base_drawers_800",
+        "transform_mm": { "position_mm": { "x": 1300, "y": 1500 }, "rotation_deg": 0 },
+        "dims_mm": { "width": 600, "depth": 600, "height": 720 },
+        "material_slots": {}
+    });
+    let codes = |state: &serde_json::Value| -> Vec<String> {
+        let response = validate_layout_json(state.to_string());
+        let value: serde_json::Value = serde_json::from_str(&response).unwrap();
+        value["violations"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|v| v["code"].as_str().unwrap().to_string())
+            .collect()
+    };
+
+    // Bounding box (500, 1000)-(1349, 1849), centre (924, 1424): the bounding boxes overlap,
+    // but the neighbour's near corner lies outside the diamond's upper-right edge
+    // (x + y = 2773).
+    state["layout"]["objects"] = serde_json::json!([corner_unit(500), neighbour.clone()]);
+    assert!(!codes(&state).iter().any(|c| c == "layout.collision"));
+
+    state["layout"]["objects"] = serde_json::json!([corner_unit(560), neighbour]);
+    assert!(codes(&state).iter().any(|c| c == "layout.collision"));
+}
+
+#[test]
+fn quarter_turns_keep_the_anchor_corner() {
+    use planforge_core_wasm::constraints::build_footprints;
+    use planforge_core_wasm::model::layout::LayoutObject;
+
+    let object = |rotation: i32| -> LayoutObject {
+        serde_json::from_value(serde_json::json!({
+            "id": "obj_a",
+            "kind": "module",
+            "catalog_item_id": "base_800",
+            "transform_mm": { "position_mm": { "x": 1000, "y": 500 }, "rotation_deg": rotation },
+            "dims_mm": { "width": 800, "depth": 600, "height": 720 },
+            "material_slots": {}
+        }))
+        .unwrap()
+    };
+
+    // 90° and 270° swap width and depth; the bounding box never leaves `position_mm`.
+    for (rotation, max_x, max_y) in [(0, 1800, 1100), (90, 1600, 1300), (180, 1800, 1100), (270, 1600, 1300)] {
+        let fp = &build_footprints(&[object(rotation)])[0];
+        let aabb = (fp.aabb.min_x, fp.aabb.min_y, fp.aabb.max_x, fp.aabb.max_y);
+        assert_eq!(aabb, (1000, 500, max_x, max_y), "rotation {rotation}");
+        assert_eq!((fp.width, fp.depth), (max_x - 1000, max_y - 500), "rotation {rotation}");
+    }
+}
+
+#[test]
+fn restricted_zone_polygon_is_exact() {
+    let mut state = base_state();
+    // L-shaped radiator zone hugging the south-west corner; its bounding box covers the
+    // whole 1200 x 1200 corner.
+    state["room"]["restricted_zones"] = serde_json::json!([
+        {
+            "id": "zone_radiator",
+            "reason": "radiator",
+            "polygon_mm": [
+                { "x": 0, "y": 0 }, { "x": 1200, "y": 0 }, { "x": 1200, "y": 200 },
+                { "x": 200, "y": 200 }, { "x": 200, "y": 1200 }, { "x": 0, "y": 1200 }
+            ]
+        }
+    ]);
+    state["layout"]["objects"] = serde_json::json!([
+        {
+            "id": "obj_clear",
+            "kind": "module",
+            "catalog_item_id": "base_sink_600",
+            "transform_mm": { "position_mm": { "x": 400, "y": 400 }, "rotation_deg": 0 },
+            "dims_mm": { "width": 600, "depth": 600, "height": 720 },
+            "material_slots": {}
+        },
+        {
+            "id": "obj_overlap",
+            "kind": "module",
+            "catalog_item_id": "base_drawers_800",
+            "transform_mm": { "position_mm": { "x": 1100, "y": 100 }, "rotation_deg": 0 },
+            "dims_mm": { "width": 600, "depth": 600, "height": 720 },
+            "material_slots": {}
+        }
+    ]);
+
+    let response = validate_layout_json(state.to_string());
+    let value: serde_json::Value = serde_json::from_str(&response).unwrap();
+    let zone_hits: Vec<&serde_json::Value> = value["violations"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|v| v["code"] == "layout.restricted_zone")
+        .collect();
+    assert_eq!(zone_hits.len(), 1);
+    assert_eq!(zone_hits[0]["object_ids"], serde_json::json!(["obj_overlap"]));
+    assert_eq!(zone_hits[0]["details"]["zone_id"], "zone_radiator");
+    assert_eq!(zone_hits[0]["details"]["overlap_area_mm2"], 10_000);
+}
+
+#[test]
+fn wall_units_are_checked_in_3d() {
+    let mut state = base_state();
+    state["room"]["openings"] = serde_json::json!([
+        {
+            "id": "door_1",
+            "kind": "door",
+            "wall_id": "south",
+            "offset_mm": 2000,
+            "width_mm": 900,
+            "height_mm": 2100,
+            "swing": { "direction": "left", "radius_mm": 900 }
+        }
+    ]);
+    let object = |id: &str, x: i32, elevation: i32, height: i32| {
+        serde_json::json!({
+            "id": id,
+            "kind": "module",
+            "catalog_item_id": "cabinet_600",
+            "transform_mm": { "position_mm": { "x": x, "y": 0 }, "rotation_deg": 0, "elevation_mm": elevation },
+            "dims_mm": { "width": 600, "depth": 350, "height": height },
+            "material_slots": {}
+        })
+    };
+    state["layout"]["objects"] = serde_json::json!([
+        object("obj_base", 0, 0, 720),
+        object("obj_wall_above", 0, 1400, 700),
+        object("obj_wall_low", 300, 600, 700),
+        object("obj_over_door", 2100, 2150, 400),
+        object("obj_through_ceiling", 1000, 2200, 700),
+    ]);
+
+    let response = validate_layout_json(state.to_string());
+    let value: serde_json::Value = serde_json::from_str(&response).unwrap();
+    let violations = value["violations"].as_array().unwrap();
+    let hits = |code: &str, id: &str| {
+        violations
+            .iter()
+            .any(|v| v["code"] == code && v["object_ids"].as_array().unwrap().iter().any(|o| o == id))
+    };
+
+    assert!(!hits("layout.collision", "obj_wall_above"));
+    assert!(hits("layout.collision", "obj_wall_low"));
+    assert!(!hits("layout.door_clearance", "obj_over_door"));
+    assert!(hits("layout.object_too_large", "obj_through_ceiling"));
+}
+
+#[test]
+fn window_rules_consider_sill_and_heights() {
+    let mut state = base_state();
+    state["room"]["openings"] = serde_json::json!([
+        {
+            "id": "window_1",
+            "kind": "window",
+            "wall_id": "south",
+            "offset_mm": 500,
+            "width_mm": 2400,
+            "height_mm": 1200,
+            "sill_height_mm": 1000
+        }
+    ]);
+    let object = |id: &str, x: i32, elevation: i32, height: i32, tags: &[&str]| {
+        serde_json::json!({
+            "id": id,
+            "kind": "module",
+            "catalog_item_id": "cabinet_600",
+            "transform_mm": { "position_mm": { "x": x, "y": 0 }, "rotation_deg": 0, "elevation_mm": elevation },
+            "dims_mm": { "width": 600, "depth": 600, "height": height },
+            "material_slots": {},
+            "tags": tags
+        })
+    };
+    state["layout"]["objects"] = serde_json::json!([
+        object("obj_base", 500, 0, 900, &["base"]),
+        object("obj_hob", 1100, 0, 900, &["base", "hob"]),
+        object("obj_high_worktop", 1700, 0, 1050, &["base"]),
+        object("obj_wall_unit", 500, 1400, 700, &["wall"]),
+        object("obj_tall", 2300, 0, 2100, &["tall"]),
+    ]);
+
+    let response = validate_layout_json(state.to_string());
+    let value: serde_json::Value = serde_json::from_str(&response).unwrap();
+    let window_codes: Vec<(String, String)> = value["violations"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|v| v["details"]["opening_id"] == "window_1")
+        .map(|v| (v["code"].as_str().unwrap().to_string(), v["object_ids"][0].as_str().unwrap().to_string()))
+        .collect();
+
+    let expected = [
+        ("layout.hob_under_window", "obj_hob"),
+        ("layout.window_worktop_above_sill", "obj_high_worktop"),
+        ("layout.window_wall_unit", "obj_wall_unit"),
+        ("layout.window_tall_unit", "obj_tall"),
+    ];
+    assert_eq!(window_codes.len(), expected.len(), "{:?}", window_codes);
+    for (code, id) in expected {
+        assert!(window_codes.iter().any(|(c, o)| c == code && o == id), "missing {} for {}", code, id);
+    }
+}
+
+#[test]
+fn decor_under_window_is_not_a_worktop() {
+    let mut state = base_state();
+    state["room"]["openings"] = serde_json::json!([
+        {
+            "id": "window_1",
+            "kind": "window",
+            "wall_id": "south",
+            "offset_mm": 500,
+            "width_mm": 1200,
+            "height_mm": 1200,
+            "sill_height_mm": 900
+        }
+    ]);
+    state["layout"]["objects"] = serde_json::json!([
+        {
+            "id": "obj_plant",
+            "kind": "decor",
+            "catalog_item_id": "plant_1200",
+            "transform_mm": { "position_mm": { "x": 800, "y": 0 }, "rotation_deg": 0 },
+            "dims_mm": { "width": 400, "depth": 400, "height": 1200 },
+            "material_slots": {}
+        }
+    ]);
+
+    let response = validate_layout_json(state.to_string());
+    let value: serde_json::Value = serde_json::from_str(&response).unwrap();
+    let violations = value["violations"].as_array().unwrap();
+    assert!(
+        !violations.iter().any(|v| v["code"] == "layout.window_worktop_above_sill"),
+        "{:?}",
+        violations
+    );
+}
+
+#[test]
+fn peninsula_under_window_keeps_worktop_below_sill() {
+    let mut state = base_state();
+    state["room"]["openings"] = serde_json::json!([
+        {
+            "id": "window_1",
+            "kind": "window",
+            "wall_id": "south",
+            "offset_mm": 500,
+            "width_mm": 1200,
+            "height_mm": 1200,
+            "sill_height_mm": 900
+        }
+    ]);
+    state["layout"]["objects"] = serde_json::json!([
+        {
+            "id": "obj_peninsula",
+            "kind": "peninsula",
+            "catalog_item_id": "peninsula_1800",
+            "transform_mm": { "position_mm": { "x": 800, "y": 0 }, "rotation_deg": 0 },
+            "dims_mm": { "width": 900, "depth": 1800, "height": 940 },
+            "material_slots": {}
+        }
+    ]);
+
+    let response = validate_layout_json(state.to_string());
+    let value: serde_json::Value = serde_json::from_str(&response).unwrap();
+    let violations = value["violations"].as_array().unwrap();
+    assert!(
+        violations
+            .iter()
+            .any(|v| v["code"] == "layout.window_worktop_above_sill" && v["object_ids"][0] == "obj_peninsula"),
+        "{:?}",
+        violations
+    );
+}
+
+#[test]
+fn door_swing_follows_hinge_side() {
+    let blocked = |swing: serde_json::Value, x: i32, y: i32| {
+        let mut state = base_state();
+        state["room"]["openings"] = serde_json::json!([
+            {
+                "id": "door_1",
+                "kind": "door",
+                "wall_id": "south",
+                "offset_mm": 1000,
+                "width_mm": 900,
+                "height_mm": 2100,
+                "sill_height_mm": 0,
+                "swing": swing
+            }
+        ]);
+        state["layout"]["objects"] = serde_json::json!([
+            {
+                "id": "obj_a",
+                "kind": "decor",
+                "catalog_item_id": "stool",
+                "transform_mm": { "position_mm": { "x": x, "y": y }, "rotation_deg": 0 },
+                "dims_mm": { "width": 200, "depth": 200, "height": 700 },
+                "material_slots": {}
+            }
+        ]);
+        let response = validate_layout_json(state.to_string());
+        let value: serde_json::Value = serde_json::from_str(&response).unwrap();
+        value["violations"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|v| v["code"] == "layout.door_clearance")
+    };
+
+    // Seen from inside, the left jamb of a south-wall door is its east end (x = 1900), so the
+    // leaf sweeps a quarter circle around it and the far corner near (1000, 900) stays free.
+    let left = serde_json::json!({ "direction": "left", "radius_mm": 900 });
+    assert!(!blocked(left.clone(), 1000, 700));
+    assert!(blocked(left, 1600, 500));
+
+    let right = serde_json::json!({ "direction": "right", "radius_mm": 900 });
+    assert!(blocked(right.clone(), 1000, 700));
+    assert!(!blocked(right, 1700, 700));
+
+    let both = serde_json::json!({ "direction": "both", "radius_mm": 450 });
+    assert!(!blocked(both.clone(), 1350, 300));
+    assert!(blocked(both.clone(), 1000, 100));
+    assert!(blocked(both, 1700, 100));
+
+    let outward = serde_json::json!({ "direction": "left", "radius_mm": 900, "opens": "outward" });
+    assert!(!blocked(outward, 1500, 100));
+}
+
+#[test]
+fn gas_and_power_rules() {
+    let mut state = base_state();
+    state["room"]["utilities"] = serde_json::json!([
+        { "id": "gas_1", "kind": "gas", "position_mm": { "x": 2000, "y": 0 }, "zone_radius_mm": 400 },
+        { "id": "power_1", "kind": "power", "position_mm": { "x": 600, "y": 0 }, "zone_radius_mm": 300 },
+        { "id": "water_1", "kind": "water", "position_mm": { "x": 2200, "y": 0 }, "zone_radius_mm": 300 }
+    ]);
+    let object = |id: &str, x: i32, y: i32, tags: &[&str]| {
+        serde_json::json!({
+            "id": id,
+            "kind": "module",
+            "catalog_item_id": "base_600",
+            "transform_mm": { "position_mm": { "x": x, "y": y }, "rotation_deg": 0 },
+            "dims_mm": { "width": 600, "depth": 600, "height": 720 },
+            "material_slots": {},
+            "tags": tags
+        })
+    };
+    state["layout"]["objects"] = serde_json::json!([
+        object("obj_oven", 600, 0, &["base", "oven"]),
+        object("obj_dishwasher", 1300, 0, &["base", "dishwasher"]),
+        object("obj_gas_hob", 2000, 0, &["base", "hob", "gas"]),
+        object("obj_gas_hob_far", 100, 1500, &["gas_hob"]),
+    ]);
+
+    let response = validate_layout_json(state.to_string());
+    let value: serde_json::Value = serde_json::from_str(&response).unwrap();
+    let violations = value["violations"].as_array().unwrap();
+    let ids_for = |code: &str| -> Vec<&str> {
+        violations
+            .iter()
+            .filter(|v| v["code"] == code)
+            .flat_map(|v| v["object_ids"].as_array().unwrap().iter().map(|id| id.as_str().unwrap()))
+            .collect()
+    };
+
+    assert_eq!(ids_for("layout.gas_hob_near_gas"), ["obj_gas_hob_far"]);
+    assert_eq!(ids_for("layout.appliance_near_power"), ["obj_dishwasher"]);
+
+    let separations: Vec<&serde_json::Value> = violations
+        .iter()
+        .filter(|v| v["code"].as_str().unwrap().ends_with("_separation"))
+        .collect();
+    assert_eq!(separations.len(), 1, "{:?}", separations);
+    assert_eq!(separations[0]["code"], "room.gas_water_separation");
+    assert_eq!(separations[0]["details"]["utility_ids"], serde_json::json!(["gas_1", "water_1"]));
+    assert_eq!(separations[0]["details"]["distance_mm"], 200);
+    assert_eq!(separations[0]["details"]["min_distance_mm"], 300);
+}
+
+#[test]
+fn front_access_zones_follow_rotation() {
+    let mut state = base_state();
+    state["room"]["restricted_zones"] = serde_json::json!([
+        { "id": "zone_1", "reason": "radiator", "aabb_mm": { "min_mm": { "x": 2100, "y": 100 }, "max_mm": { "x": 2200, "y": 200 } } }
+    ]);
+    let object = |id: &str, kind: &str, x: i32, y: i32, rotation: i32, tags: &[&str]| {
+        serde_json::json!({
+            "id": id,
+            "kind": kind,
+            "catalog_item_id": "base_600",
+            "transform_mm": { "position_mm": { "x": x, "y": y }, "rotation_deg": rotation },
+            "dims_mm": { "width": 600, "depth": 600, "height": 720 },
+            "material_slots": {},
+            "tags": tags
+        })
+    };
+    state["layout"]["objects"] = serde_json::json!([
+        // Door drop reaches 600 mm; the island 400 mm away faces back towards it.
+        object("obj_dishwasher", "appliance", 0, 0, 0, &["dishwasher"]),
+        object("obj_island", "module", 0, 1000, 180, &["base"]),
+        // Turned to face west, against the east wall; its door swings over the radiator.
+        object("obj_oven", "appliance", 2600, 0, 90, &["oven"]),
+        // Standing against the north wall but facing it.
+        object("obj_facing_wall", "module", 1200, 2000, 0, &["base"]),
+        object("obj_plant", "decor", 1200, 0, 0, &[]),
+    ]);
+
+    let response = validate_layout_json(state.to_string());
+    let value: serde_json::Value = serde_json::from_str(&response).unwrap();
+    let mut blocked: Vec<(String, String)> = value["violations"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|v| v["code"] == "layout.front_access_blocked")
+        .map(|v| {
+            (
+                v["object_ids"][0].as_str().unwrap().to_string(),
+                v["details"]["blocked_by"].as_str().unwrap().to_string(),
+            )
+        })
+        .collect();
+    blocked.sort();
+
+    let expected = [
+        ("obj_dishwasher", "obj_island"),
+        ("obj_facing_wall", "north"),
+        ("obj_oven", "zone_1"),
+    ];
+    assert_eq!(blocked.len(), expected.len(), "{:?}", blocked);
+    for ((owner, by), (expected_owner, expected_by)) in blocked.iter().zip(expected) {
+        assert_eq!((owner.as_str(), by.as_str()), (expected_owner, expected_by));
+    }
+}
+
+#[test]
+fn modules_must_be_wall_backed_or_in_a_run() {
+    let mut state = base_state();
+    let object = |id: &str, x: i32, y: i32, rotation: i32, tags: &[&str]| {
+        serde_json::json!({
+            "id": id,
+            "kind": "module",
+            "catalog_item_id": "base_600",
+            "transform_mm": { "position_mm": { "x": x, "y": y }, "rotation_deg": rotation },
+            "dims_mm": { "width": 600, "depth": 600, "height": 720 },
+            "material_slots": {},
+            "tags": tags
+        })
+    };
+    state["layout"]["objects"] = serde_json::json!([
+        object("obj_a", 0, 0, 0, &["base"]),
+        // Facing east with its back against obj_a's side, then a run continuing north from it.
+        object("obj_b", 600, 0, 270, &["base"]),
+        object("obj_c", 600, 600, 270, &["base"]),
+        object("obj_d", 600, 1230, 270, &["base"]),
+        object("obj_float", 2000, 1000, 0, &["base"]),
+        object("obj_island", 1900, 2000, 0, &["island"]),
+        object("obj_wall_gap", 2580, 0, 0, &["base"]),
+    ]);
+
+    let response = validate_layout_json(state.to_string());
+    let value: serde_json::Value = serde_json::from_str(&response).unwrap();
+    let violations = value["violations"].as_array().unwrap();
+
+    let floating: Vec<&str> = violations
+        .iter()
+        .filter(|v| v["code"] == "layout.floating_object")
+        .map(|v| v["object_ids"][0].as_str().unwrap())
+        .collect();
+    assert_eq!(floating, ["obj_float"]);
+
+    let fillers: Vec<&serde_json::Value> = violations
+        .iter()
+        .filter(|v| v["code"] == "layout.filler_required")
+        .collect();
+    assert_eq!(fillers.len(), 2, "{:?}", fillers);
+    assert!(fillers.iter().all(|v| v["severity"] == "warning"));
+    assert_eq!(fillers[0]["object_ids"], serde_json::json!(["obj_c", "obj_d"]));
+    assert_eq!(fillers[0]["details"]["gap_mm"], 30);
+    assert_eq!(fillers[1]["object_ids"], serde_json::json!(["obj_wall_gap"]));
+    assert_eq!(fillers[1]["details"]["gap_mm"], 20);
+    assert_eq!(fillers[1]["details"]["wall_id"], "east");
+}
+
+fn unit(id: &str, kind: &str, x: i32, y: i32, width: i32, depth: i32, tags: &[&str]) -> serde_json::Value {
+    serde_json::json!({
+        "id": id,
+        "kind": kind,
+        "catalog_item_id": "base_600",
+        "transform_mm": { "position_mm": { "x": x, "y": y }, "rotation_deg": 0 },
+        "dims_mm": { "width": width, "depth": depth, "height": 900 },
+        "material_slots": {},
+        "tags": tags
+    })
+}
+
+fn island_violations(state: &serde_json::Value) -> Vec<(String, String, String, i64)> {
+    let response = validate_layout_json(state.to_string());
+    let value: serde_json::Value = serde_json::from_str(&response).unwrap();
+    let mut found: Vec<(String, String, String, i64)> = value["violations"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|v| v["code"].as_str().unwrap().starts_with("layout.island_"))
+        .map(|v| {
+            let details = &v["details"];
+            (
+                v["code"].as_str().unwrap().to_string(),
+                details["face"].as_str().unwrap_or("").to_string(),
+                details["blocked_by"].as_str().unwrap_or(v["object_ids"][1].as_str().unwrap_or("")).to_string(),
+                details["walkway_mm"].as_i64().unwrap_or(-1),
+            )
+        })
+        .collect();
+    found.sort();
+    found
+}
+
+#[test]
+fn island_needs_walkway_on_every_open_side() {
+    let mut state = base_state();
+    // 950 mm from the island's front to the north wall: wide enough for a passage, not for an
+    // island walkway.
+    state["room"]["size_mm"]["depth"] = serde_json::json!(3050);
+    state["layout"]["objects"] = serde_json::json!([
+        unit("obj_run", "module", 0, 0, 3200, 600, &["base"]),
+        // Seating on the south side: 300 mm overhang, then only 600 mm to the run.
+        unit("obj_island", "island", 1000, 1500, 1200, 600, &["seating"]),
+        unit("obj_bin", "module", 1300, 1250, 200, 200, &[]),
+    ]);
+
+    let found = island_violations(&state);
+    let expected = [
+        ("layout.island_seating_blocked", "", "obj_bin", -1),
+        ("layout.island_walkway", "back", "obj_run", 600),
+        ("layout.island_walkway", "front", "north", 950),
+    ];
+    assert_eq!(found.len(), expected.len(), "{:?}", found);
+    for (got, (code, face, by, walkway)) in found.iter().zip(expected) {
+        assert_eq!((got.0.as_str(), got.1.as_str(), got.2.as_str(), got.3), (code, face, by, walkway));
+    }
+}
+
+#[test]
+fn peninsula_is_checked_on_its_free_sides_only() {
+    let mut state = base_state();
+    state["room"]["size_mm"]["depth"] = serde_json::json!(3200);
+    state["layout"]["objects"] = serde_json::json!([
+        unit("obj_run", "module", 0, 0, 3200, 600, &["base"]),
+        // Joined to the west wall; 950 mm from the run behind it, 1050 mm from the north wall.
+        unit("obj_peninsula", "module", 0, 1550, 1200, 600, &["peninsula"]),
+    ]);
+
+    let found = island_violations(&state);
+    assert_eq!(found.len(), 1, "{:?}", found);
+    assert_eq!(
+        (found[0].0.as_str(), found[0].1.as_str(), found[0].2.as_str(), found[0].3),
+        ("layout.island_walkway", "back", "obj_run", 950)
+    );
+}
+
+#[test]
+fn island_gap_below_min_passage_is_reported_once() {
+    let mut state = base_state();
+    state["layout"]["objects"] = serde_json::json!([
+        unit("obj_run", "module", 0, 0, 3200, 600, &["base"]),
+        unit("obj_peninsula", "module", 0, 900, 1200, 600, &["peninsula"]),
+    ]);
+
+    let response = validate_layout_json(state.to_string());
+    let value: serde_json::Value = serde_json::from_str(&response).unwrap();
+    let gaps: Vec<&serde_json::Value> = value["violations"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|v| v["code"] == "layout.min_passage" || v["code"] == "layout.island_walkway")
+        .collect();
+    assert_eq!(gaps.len(), 1, "{:?}", gaps);
+    assert_eq!(gaps[0]["code"], "layout.min_passage");
+    assert_eq!(gaps[0]["details"]["width_mm"], 300);
+
+    // With the passage rule off, the island rule reports the gap itself.
+    state["project"]["disabled_constraints"] = serde_json::json!(["min_passage"]);
+    let found = island_violations(&state);
+    assert_eq!(found.len(), 1, "{:?}", found);
+    assert_eq!(
+        (found[0].0.as_str(), found[0].1.as_str(), found[0].2.as_str(), found[0].3),
+        ("layout.island_walkway", "back", "obj_run", 300)
+    );
+}
+
+#[test]
+fn passages_are_measured_to_walls_and_across_diagonals() {
+    let mut state = base_state();
+    state["layout"]["objects"] = serde_json::json!([
+        unit("obj_run", "module", 0, 0, 3200, 600, &["base"]),
+        // 100 mm short of the north wall.
+        unit("obj_a", "module", 0, 1900, 600, 600, &["base"]),
+        // Offset from obj_a by 400 mm across and 300 mm down: 500 mm corner to corner.
+        unit("obj_d", "module", 1000, 1000, 600, 600, &["base"]),
+    ]);
+
+    let response = validate_layout_json(state.to_string());
+    let value: serde_json::Value = serde_json::from_str(&response).unwrap();
+    let mut passages: Vec<(String, i64)> = value["violations"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|v| v["code"] == "layout.min_passage")
+        .map(|v| {
+            let mut between: Vec<&str> = v["object_ids"].as_array().unwrap().iter().map(|id| id.as_str().unwrap()).collect();
+            between.sort();
+            if let Some(wall) = v["details"]["wall_id"].as_str() {
+                between.push(wall);
+            }
+            (between.join("/"), v["details"]["width_mm"].as_i64().unwrap())
+        })
+        .collect();
+    passages.sort();
+
+    assert_eq!(
+        passages,
+        [
+            ("obj_a/north".to_string(), 100),
+            ("obj_a/obj_d".to_string(), 500),
+            ("obj_d/obj_run".to_string(), 400),
+        ]
+    );
+}
+
+#[test]
+fn gaps_along_a_filler_slit_are_not_passages() {
+    let between_a_and_c = |objects: serde_json::Value| -> usize {
+        let mut state = base_state();
+        state["layout"]["objects"] = objects;
+        let response = validate_layout_json(state.to_string());
+        let value: serde_json::Value = serde_json::from_str(&response).unwrap();
+        value["violations"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter(|v| v["code"] == "layout.min_passage" && v["object_ids"] == serde_json::json!(["obj_a", "obj_c"]))
+            .count()
+    };
+    let a = unit("obj_a", "module", 0, 1000, 500, 500, &["base"]);
+    let c = unit("obj_c", "module", 1040, 1520, 500, 500, &["base"]);
+    assert_eq!(between_a_and_c(serde_json::json!([a.clone(), c.clone()])), 1);
+
+    // With obj_b next to obj_a, the 540 mm line from obj_a to obj_c runs along the 20 mm slit
+    // between obj_b and obj_c.
+    let b = unit("obj_b", "module", 520, 1000, 500, 500, &["base"]);
+    assert_eq!(between_a_and_c(serde_json::json!([a, b, c])), 0);
+}
@@ -0,0 +1,71 @@
+use planforge_core_wasm::{validate_drag_json, validate_layout_json};
+
+fn object(id: &str, x: i32, y: i32, tags: &[&str]) -> serde_json::Value {
+    serde_json::json!({
+        "id": id,
+        "kind": "module",
+        "catalog_item_id": "base_600",
+        "transform_mm": { "position_mm": { "x": x, "y": y }, "rotation_deg": 0 },
+        "dims_mm": { "width": 600, "depth": 600, "height": 720 },
+        "material_slots": {},
+        "tags": tags
+    })
+}
+
+fn state() -> serde_json::Value {
+    serde_json::json!({
+        "schema_version": "0.1.0",
+        "project": { "project_id": "p1", "revision_id": "r1", "units": "mm" },
+        "room": {
+            "size_mm": { "width": 4000, "depth": 3000, "height": 2700 },
+            "openings": [
+                { "id": "door_1", "kind": "door", "wall_id": "south", "offset_mm": 0, "width_mm": 900, "height_mm": 2100 }
+            ],
+            "utilities": [
+                { "id": "water_1", "kind": "water", "wall_id": "north", "offset_mm": 3000, "zone_radius_mm": 500 }
+            ],
+            "restricted_zones": [
+                { "id": "zone_1", "reason": "pillar", "aabb_mm": { "min_mm": { "x": 3500, "y": 0 }, "max_mm": { "x": 4000, "y": 500 } } }
+            ]
+        },
+        "layout": {
+            "objects": [
+                object("obj_a", 100, 0, &["sink"]),
+                object("obj_b", 500, 0, &[]),
+                object("obj_c", 1500, 0, &[]),
+                object("obj_d", 3300, 0, &["hob"]),
+                object("obj_e", 3300, 1000, &[])
+            ]
+        },
+        "catalog_refs": { "modules_catalog_version": "modules_demo_0.1.0", "materials_catalog_version": "materials_demo_0.1.0" }
+    })
+}
+
+fn violations(response: String) -> Vec<serde_json::Value> {
+    let value: serde_json::Value = serde_json::from_str(&response).unwrap();
+    value["violations"].as_array().unwrap().clone()
+}
+
+#[test]
+fn drag_mode_matches_full_run_for_moved_objects() {
+    let full = violations(validate_layout_json(state().to_string()));
+
+    for moved in [vec!["obj_a"], vec!["obj_c"], vec!["obj_b", "obj_d"]] {
+        let expected: Vec<serde_json::Value> = full
+            .iter()
+            .filter(|v| {
+                v["object_ids"]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .any(|id| moved.contains(&id.as_str().unwrap()))
+            })
+            .cloned()
+            .collect();
+        assert!(!expected.is_empty());
+
+        let ids = serde_json::json!(moved).to_string();
+        let drag = violations(validate_drag_json(state().to_string(), ids));
+        assert_eq!(drag, expected, "moved {:?}", moved);
+    }
+}
@@ -0,0 +1,69 @@
+use planforge_core_wasm::geometry::aabb::Aabb;
+use planforge_core_wasm::geometry::obb::{Obb, Vec2};
+
+#[test]
+fn axis_aligned_boxes_match_aabb_semantics() {
+    let a = Obb::from_aabb(&Aabb::from_min_max(0, 0, 600, 600));
+    let touching = Obb::from_aabb(&Aabb::from_min_max(600, 0, 1200, 600));
+    let overlapping = Obb::from_aabb(&Aabb::from_min_max(599, 0, 1200, 600));
+    assert!(!a.intersects(&touching));
+    assert!(a.intersects(&overlapping));
+    assert_eq!(a.distance(&touching), 0.0);
+}
+
+#[test]
+fn rotated_box_separates_where_aabbs_overlap() {
+    // A 600x600 square turned 45° reaches ~424mm from its centre along the axes, but its
+    // bounding box corner at (+300, +300) is empty space.
+    let diamond = Obb::new(Vec2::new(0.0, 0.0), 600.0, 600.0, 45);
+    let near_corner = Obb::from_aabb(&Aabb::from_min_max(250, 250, 850, 850));
+    assert!(diamond.aabb().intersects(&near_corner.aabb()));
+    assert!(!diamond.intersects(&near_corner));
+
+    let on_axis = Obb::from_aabb(&Aabb::from_min_max(400, -100, 1000, 100));
+    assert!(diamond.intersects(&on_axis));
+}
+
+#[test]
+fn distance_between_rotated_and_axis_aligned() {
+    let diamond = Obb::new(Vec2::new(0.0, 0.0), 600.0, 600.0, 45);
+    let right = Obb::from_aabb(&Aabb::from_min_max(1000, -100, 1600, 100));
+    let expected = 1000.0 - 300.0 * 2f64.sqrt();
+    assert!((diamond.distance(&right) - expected).abs() < 1e-6);
+}
+
+#[test]
+fn quarter_turns_swap_extents_about_centre() {
+    let obb = Obb::new(Vec2::new(500.0, 300.0), 1000.0, 600.0, 90);
+    let aabb = obb.aabb();
+    assert_eq!((aabb.min_x, aabb.min_y, aabb.max_x, aabb.max_y), (200, -200, 800, 800));
+    assert!(obb.is_axis_aligned());
+}
+
+#[test]
+fn containment() {
+    let room = Obb::from_aabb(&Aabb::from_min_max(0, 0, 3000, 3000));
+    assert!(room.contains(&Obb::new(Vec2::new(1500.0, 1500.0), 600.0, 600.0, 30)));
+    assert!(!room.contains(&Obb::new(Vec2::new(200.0, 200.0), 600.0, 600.0, 45)));
+}
+
+#[test]
+fn closest_points_centre_on_facing_stretch() {
+    let a = Obb::from_aabb(&Aabb::from_min_max(0, 0, 600, 600));
+    let b = Obb::from_aabb(&Aabb::from_min_max(1000, 300, 1600, 900));
+    let (p, q) = a.closest_points(&b);
+    assert!((p.x - 600.0).abs() < 1e-6 && (q.x - 1000.0).abs() < 1e-6);
+    assert!((p.y - q.y).abs() < 1e-6 && p.y > 300.0 && p.y < 600.0);
+
+    let (p, q) = a.closest_points_to_segment(Vec2::new(0.0, 1000.0), Vec2::new(2000.0, 1000.0));
+    assert!((p.y - 600.0).abs() < 1e-6 && (q.y - 1000.0).abs() < 1e-6);
+    assert!((p.x - q.x).abs() < 1e-6 && p.x > 0.0 && p.x < 600.0);
+}
+
+#[test]
+fn point_distance_is_zero_inside() {
+    let diamond = Obb::new(Vec2::new(0.0, 0.0), 600.0, 600.0, 45);
+    assert_eq!(diamond.point_distance(Vec2::new(100.0, 100.0)), 0.0);
+    // The corner on the x axis is ~424 mm out.
+    assert!((diamond.point_distance(Vec2::new(524.264, 0.0)) - 100.0).abs() < 0.01);
+}
@@ -0,0 +1,57 @@
+use planforge_core_wasm::geometry::obb::{Obb, Vec2};
+use planforge_core_wasm::geometry::polygon::Polygon;
+use planforge_core_wasm::geometry::sector::Sector;
+
+fn l_shape() -> Polygon {
+    // 1000 x 1000 square with the top-right 600 x 600 quadrant removed.
+    Polygon::new(vec![
+        Vec2::new(0.0, 0.0),
+        Vec2::new(1000.0, 0.0),
+        Vec2::new(1000.0, 400.0),
+        Vec2::new(400.0, 400.0),
+        Vec2::new(400.0, 1000.0),
+        Vec2::new(0.0, 1000.0),
+    ])
+}
+
+#[test]
+fn concave_notch_is_not_overlap() {
+    let in_notch = Obb::new(Vec2::new(700.0, 700.0), 400.0, 400.0, 0);
+    assert!(!l_shape().intersects_obb(&in_notch));
+    assert_eq!(l_shape().overlap_area_mm2(&in_notch), 0.0);
+}
+
+#[test]
+fn overlap_area_of_concave_polygon() {
+    // Covers x 200..800, y 200..800: the L contributes 600x200 + 200x400.
+    let rect = Obb::new(Vec2::new(500.0, 500.0), 600.0, 600.0, 0);
+    assert!((l_shape().overlap_area_mm2(&rect) - 200_000.0).abs() < 1e-6);
+    assert!(l_shape().intersects_obb(&rect));
+}
+
+#[test]
+fn touching_edge_does_not_intersect() {
+    let beside = Obb::new(Vec2::new(1300.0, 200.0), 600.0, 400.0, 0);
+    assert!(!l_shape().intersects_obb(&beside));
+}
+
+#[test]
+fn rotated_rectangle_against_diagonal_polygon() {
+    let diagonal = Polygon::new(vec![Vec2::new(0.0, 0.0), Vec2::new(1000.0, 0.0), Vec2::new(0.0, 1000.0)]);
+    let clear = Obb::new(Vec2::new(800.0, 800.0), 400.0, 200.0, 135);
+    let crossing = Obb::new(Vec2::new(500.0, 500.0), 400.0, 200.0, 135);
+    assert!(!diagonal.intersects_obb(&clear));
+    // Width runs along the hypotenuse, so exactly half the rectangle is inside.
+    assert!((diagonal.overlap_area_mm2(&crossing) - 40_000.0).abs() < 1e-6);
+}
+
+#[test]
+fn sector_excludes_corner_outside_arc() {
+    let quarter = Sector::new(Vec2::new(0.0, 0.0), 1000.0, Vec2::new(0.0, 1.0), Vec2::new(1.0, 0.0));
+    assert!(!quarter.intersects_obb(&Obb::new(Vec2::new(850.0, 850.0), 200.0, 200.0, 0)));
+    assert!(quarter.intersects_obb(&Obb::new(Vec2::new(600.0, 600.0), 200.0, 200.0, 0)));
+    // Behind the hinge, outside the wedge.
+    assert!(!quarter.intersects_obb(&Obb::new(Vec2::new(-300.0, 300.0), 200.0, 200.0, 0)));
+    // Touching the bounding ray only.
+    assert!(!quarter.intersects_obb(&Obb::new(Vec2::new(300.0, -100.0), 200.0, 200.0, 0)));
+}
@@ -0,0 +1,75 @@
+use planforge_core_wasm::geometry::aabb::Aabb;
+use planforge_core_wasm::geometry::sweep::{sweep_pairs, sweep_pairs_between};
+
+fn brute_force(boxes: &[Aabb], margin: i32) -> Vec<(usize, usize)> {
+    let mut pairs = Vec::new();
+    for i in 0..boxes.len() {
+        for j in i + 1..boxes.len() {
+            let (a, b) = (&boxes[i], &boxes[j]);
+            let near_x = a.overlaps_x(b) || a.gap_x(b) < margin;
+            let near_y = a.overlaps_y(b) || a.gap_y(b) < margin;
+            if near_x && near_y {
+                pairs.push((i, j));
+            }
+        }
+    }
+    pairs
+}
+
+#[test]
+fn sweep_matches_brute_force() {
+    let mut seed: u64 = 7;
+    let mut next = move |range: i32| {
+        seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        ((seed >> 33) % range as u64) as i32
+    };
+
+    for _ in 0..50 {
+        let boxes: Vec<Aabb> = (0..60)
+            .map(|_| {
+                let (x, y) = (next(6000), next(6000));
+                Aabb::from_min_max(x, y, x + 1 + next(900), y + 1 + next(900))
+            })
+            .collect();
+        for margin in [0, 900] {
+            assert_eq!(sweep_pairs(&boxes, margin), brute_force(&boxes, margin));
+        }
+    }
+}
+
+#[test]
+fn sweep_between_sets_matches_combined_sweep() {
+    let mut seed: u64 = 11;
+    let mut next = move |range: i32| {
+        seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        ((seed >> 33) % range as u64) as i32
+    };
+
+    for _ in 0..50 {
+        let mut random_boxes = |count: usize| -> Vec<Aabb> {
+            (0..count)
+                .map(|_| {
+                    let (x, y) = (next(6000), next(6000));
+                    Aabb::from_min_max(x, y, x + next(900), y + next(900))
+                })
+                .collect()
+        };
+        let (left, right) = (random_boxes(40), random_boxes(15));
+        let all: Vec<Aabb> = left.iter().chain(&right).cloned().collect();
+        for margin in [1, 900] {
+            let expected: Vec<(usize, usize)> = sweep_pairs(&all, margin)
+                .into_iter()
+                .filter(|&(i, j)| i < left.len() && j >= left.len())
+                .map(|(i, j)| (i, j - left.len()))
+                .collect();
+            assert_eq!(sweep_pairs_between(&left, &right, margin), expected);
+        }
+    }
+}
+
+#[test]
+fn touching_boxes_are_not_colliding() {
+    let boxes = vec![Aabb::from_min_max(0, 0, 600, 600), Aabb::from_min_max(600, 0, 1200, 600)];
+    assert!(sweep_pairs(&boxes, 0).is_empty());
+    assert_eq!(sweep_pairs(&boxes, 1), vec![(0, 1)]);
+}
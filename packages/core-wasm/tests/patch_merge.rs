@@ -0,0 +1,117 @@
+use planforge_core_wasm::{apply_patch_json, merge_patches_json};
+
+fn object(id: &str, x: i32) -> serde_json::Value {
+    serde_json::json!({
+        "id": id,
+        "kind": "module",
+        "catalog_item_id": "base_600",
+        "transform_mm": { "position_mm": { "x": x, "y": 0 }, "rotation_deg": 0 },
+        "dims_mm": { "width": 600, "depth": 600, "height": 720 },
+        "material_slots": { "front": "mat_white" }
+    })
+}
+
+fn base_state() -> serde_json::Value {
+    serde_json::json!({
+        "schema_version": "0.1.0",
+        "project": { "project_id": "p1", "revision_id": "r1", "units": "mm" },
+        "room": {
+            "size_mm": { "width": 5000, "depth": 3000, "height": 2700 },
+            "openings": [],
+            "utilities": [],
+            "restricted_zones": []
+        },
+        "layout": { "objects": [object("obj_a", 0), object("obj_b", 600)] },
+        "catalog_refs": { "modules_catalog_version": "modules_demo_0.1.0", "materials_catalog_version": "materials_demo_0.1.0" }
+    })
+}
+
+fn merge(a: serde_json::Value, b: serde_json::Value) -> serde_json::Value {
+    let response = merge_patches_json(base_state().to_string(), a.to_string(), b.to_string());
+    serde_json::from_str(&response).unwrap()
+}
+
+#[test]
+fn disjoint_edits_merge_cleanly() {
+    let a = serde_json::json!({
+        "ops": [
+            { "op": "remove", "path": "/layout/objects/0" },
+            { "op": "add", "path": "/layout/objects/-", "value": object("obj_c", 1200) }
+        ]
+    });
+    let b = serde_json::json!({
+        "ops": [{ "op": "replace", "path": "/layout/objects/1/material_slots/front", "value": "mat_oak" }],
+        "source": "user"
+    });
+
+    let result = merge(a.clone(), b);
+    assert_eq!(result["conflicts"], serde_json::json!([]));
+    let objects = result["kitchen_state"]["layout"]["objects"].as_array().unwrap();
+    let ids: Vec<&str> = objects.iter().map(|o| o["id"].as_str().unwrap()).collect();
+    assert_eq!(ids, vec!["obj_b", "obj_c"]);
+    assert_eq!(objects[0]["material_slots"]["front"], "mat_oak");
+    assert_eq!(result["rebased_patch"]["source"], "user");
+
+    let after_a = apply_patch_json(base_state().to_string(), a.to_string());
+    let rebased = apply_patch_json(after_a, result["rebased_patch"].to_string());
+    let rebased: serde_json::Value = serde_json::from_str(&rebased).unwrap();
+    assert_eq!(rebased, result["kitchen_state"]);
+}
+
+#[test]
+fn reports_same_field_and_delete_modify_conflicts() {
+    let a = serde_json::json!({
+        "ops": [
+            { "op": "replace", "path": "/layout/objects/0/transform_mm/position_mm/x", "value": 100 },
+            { "op": "remove", "path": "/layout/objects/1" }
+        ]
+    });
+    let b = serde_json::json!({
+        "ops": [
+            { "op": "replace", "path": "/layout/objects/0/transform_mm/position_mm/x", "value": 200 },
+            { "op": "replace", "path": "/layout/objects/1/transform_mm/position_mm/x", "value": 900 }
+        ]
+    });
+
+    let result = merge(a, b);
+    let conflicts = result["conflicts"].as_array().unwrap();
+    assert_eq!(conflicts.len(), 2);
+    assert!(conflicts.iter().all(|c| c["code"] == "patch.conflict"));
+
+    let field = conflicts.iter().find(|c| c["details"]["kind"] == "field").unwrap();
+    assert_eq!(field["object_ids"], serde_json::json!(["obj_a"]));
+    assert_eq!(field["details"]["path"], "/layout/objects/[id=obj_a]/transform_mm/position_mm/x");
+    assert_eq!(field["details"]["ours"], 100);
+    assert_eq!(field["details"]["theirs"], 200);
+
+    let deleted = conflicts.iter().find(|c| c["details"]["kind"] == "delete_modify").unwrap();
+    assert_eq!(deleted["object_ids"], serde_json::json!(["obj_b"]));
+
+    assert_eq!(result["kitchen_state"]["layout"]["objects"][0]["transform_mm"]["position_mm"]["x"], 100);
+    assert_eq!(result["kitchen_state"]["layout"]["objects"].as_array().unwrap().len(), 1);
+}
+
+#[test]
+fn concurrent_appends_keep_ours_first() {
+    let a = serde_json::json!({ "ops": [{ "op": "add", "path": "/layout/objects/-", "value": object("obj_x", 1200) }] });
+    let b = serde_json::json!({ "ops": [{ "op": "add", "path": "/layout/objects/-", "value": object("obj_y", 1800) }] });
+
+    let result = merge(a.clone(), b);
+    assert_eq!(result["conflicts"], serde_json::json!([]));
+    let objects = result["kitchen_state"]["layout"]["objects"].as_array().unwrap();
+    let ids: Vec<&str> = objects.iter().map(|o| o["id"].as_str().unwrap()).collect();
+    assert_eq!(ids, vec!["obj_a", "obj_b", "obj_x", "obj_y"]);
+
+    let after_a = apply_patch_json(base_state().to_string(), a.to_string());
+    let rebased = apply_patch_json(after_a, result["rebased_patch"].to_string());
+    let rebased: serde_json::Value = serde_json::from_str(&rebased).unwrap();
+    assert_eq!(rebased, result["kitchen_state"]);
+}
+
+#[test]
+fn failing_patch_is_identified() {
+    let a = serde_json::json!({ "ops": [] });
+    let b = serde_json::json!({ "ops": [{ "op": "remove", "path": "/layout/objects/9" }] });
+    let result = merge(a, b);
+    assert_eq!(result["violations"][0]["details"]["patch"], "b");
+}
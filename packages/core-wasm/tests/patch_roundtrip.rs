@@ -1,4 +1,4 @@
-use planforge_core_wasm::{apply_patch_json, derive_render_model_json};
+use planforge_core_wasm::{apply_patch_json, apply_patch_with_inverse_json, derive_render_model_json};
 
 fn kitchen_state_fixture() -> String {
     r#"{
@@ -63,3 +63,165 @@ fn derive_render_model_has_nodes_per_object() {
     let nodes = value.get("nodes").and_then(|v| v.as_array()).unwrap();
     assert_eq!(nodes.len(), 1);
 }
+
+fn object_ids(state: &serde_json::Value) -> Vec<String> {
+    state["layout"]["objects"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|o| o["id"].as_str().unwrap().to_string())
+        .collect()
+}
+
+fn violation_codes(response: &serde_json::Value) -> Vec<String> {
+    response["violations"]
+        .as_array()
+        .map(|list| list.iter().map(|v| v["code"].as_str().unwrap().to_string()).collect())
+        .unwrap_or_default()
+}
+
+#[test]
+fn apply_patch_add_appends_and_inserts() {
+    let base: serde_json::Value = serde_json::from_str(&kitchen_state_fixture()).unwrap();
+    let mut appended = base["layout"]["objects"][0].clone();
+    appended["id"] = serde_json::json!("obj_appended");
+    let mut inserted = base["layout"]["objects"][0].clone();
+    inserted["id"] = serde_json::json!("obj_inserted");
+    let patch = serde_json::json!({
+        "ops": [
+            { "op": "add", "path": "/layout/objects/-", "value": appended },
+            { "op": "add", "path": "/layout/objects/0", "value": inserted },
+            { "op": "add", "path": "/layout/objects/1/tags", "value": ["base"] }
+        ]
+    });
+
+    let updated = apply_patch_json(kitchen_state_fixture(), patch.to_string());
+    let value: serde_json::Value = serde_json::from_str(&updated).unwrap();
+    assert_eq!(object_ids(&value), vec!["obj_inserted", "obj_base_sink_600", "obj_appended"]);
+    assert_eq!(value["layout"]["objects"][1]["tags"], serde_json::json!(["base"]));
+}
+
+#[test]
+fn apply_patch_remove_move_copy() {
+    let patch = serde_json::json!({
+        "ops": [
+            { "op": "copy", "from": "/layout/objects/0", "path": "/layout/objects/-" },
+            { "op": "replace", "path": "/layout/objects/1/id", "value": "obj_copy" },
+            { "op": "move", "from": "/layout/objects/1", "path": "/layout/objects/0" },
+            { "op": "test", "path": "/layout/objects/0/id", "value": "obj_copy" },
+            { "op": "remove", "path": "/layout/objects/1" }
+        ]
+    });
+
+    let updated = apply_patch_json(kitchen_state_fixture(), patch.to_string());
+    let value: serde_json::Value = serde_json::from_str(&updated).unwrap();
+    assert_eq!(object_ids(&value), vec!["obj_copy"]);
+}
+
+#[test]
+fn apply_patch_reports_per_op_error_codes() {
+    let cases = [
+        (serde_json::json!({ "op": "test", "path": "/layout/objects/0/id", "value": "other" }), "patch.test_failed"),
+        (serde_json::json!({ "op": "remove", "path": "/layout/objects/5" }), "patch.index_out_of_bounds"),
+        (serde_json::json!({ "op": "replace", "path": "/layout/missing", "value": 1 }), "patch.path_not_found"),
+        (serde_json::json!({ "op": "add", "path": "/layout/objects/01", "value": {} }), "patch.invalid_pointer"),
+        (serde_json::json!({ "op": "move", "path": "/layout/objects/0" }), "patch.missing_from"),
+        (serde_json::json!({ "op": "move", "from": "/layout", "path": "/layout/objects" }), "patch.invalid_move"),
+        (serde_json::json!({ "op": "add", "path": "/layout/objects/-" }), "patch.missing_value"),
+    ];
+
+    for (op, expected) in cases {
+        let patch = serde_json::json!({ "ops": [op] });
+        let response = apply_patch_json(kitchen_state_fixture(), patch.to_string());
+        let value: serde_json::Value = serde_json::from_str(&response).unwrap();
+        assert_eq!(violation_codes(&value), vec![expected.to_string()], "{}", response);
+    }
+}
+
+#[test]
+fn apply_patch_is_atomic_and_names_failing_op() {
+    let patch = serde_json::json!({
+        "ops": [
+            { "op": "replace", "path": "/layout/objects/0/transform_mm/position_mm/x", "value": 1200 },
+            { "op": "remove", "path": "/layout/objects/3" },
+            { "op": "replace", "path": "/layout/objects/0/transform_mm/position_mm/y", "value": 10 }
+        ]
+    });
+
+    let response = apply_patch_json(kitchen_state_fixture(), patch.to_string());
+    let value: serde_json::Value = serde_json::from_str(&response).unwrap();
+    let violations = value["violations"].as_array().unwrap();
+    assert_eq!(violations.len(), 1);
+    let details = &violations[0]["details"];
+    assert_eq!(details["op_index"], 1);
+    assert_eq!(details["op"], "remove");
+    assert_eq!(details["path"], "/layout/objects/3");
+    assert_eq!(details["reason"], "pointer_index_out_of_bounds");
+
+    let original: serde_json::Value = serde_json::from_str(&kitchen_state_fixture()).unwrap();
+    assert_eq!(value["kitchen_state"], original);
+}
+
+#[test]
+fn inverse_patch_restores_original_state() {
+    let base: serde_json::Value = serde_json::from_str(&kitchen_state_fixture()).unwrap();
+    let mut added = base["layout"]["objects"][0].clone();
+    added["id"] = serde_json::json!("obj_added");
+    let patch = serde_json::json!({
+        "ops": [
+            { "op": "replace", "path": "/layout/objects/0/transform_mm/position_mm/x", "value": 1200 },
+            { "op": "add", "path": "/layout/objects/-", "value": added },
+            { "op": "move", "from": "/layout/objects/1", "path": "/layout/objects/0" },
+            { "op": "copy", "from": "/layout/objects/1/material_slots/front", "path": "/layout/objects/0/material_slots/body" },
+            { "op": "remove", "path": "/layout/objects/1/material_slots/top" },
+            { "op": "test", "path": "/layout/objects/0/id", "value": "obj_added" }
+        ],
+        "reason": "agent move",
+        "source": "agent"
+    });
+
+    let response = apply_patch_with_inverse_json(kitchen_state_fixture(), patch.to_string());
+    let value: serde_json::Value = serde_json::from_str(&response).unwrap();
+    let inverse = &value["inverse_patch"];
+    assert_eq!(inverse["source"], "agent");
+    assert_eq!(inverse["reason"], "undo: agent move");
+
+    let restored = apply_patch_json(value["kitchen_state"].to_string(), inverse.to_string());
+    let restored: serde_json::Value = serde_json::from_str(&restored).unwrap();
+    assert_eq!(restored, base);
+}
+
+#[test]
+fn apply_patch_resolves_id_selectors() {
+    let base: serde_json::Value = serde_json::from_str(&kitchen_state_fixture()).unwrap();
+    let mut inserted = base["layout"]["objects"][0].clone();
+    inserted["id"] = serde_json::json!("obj_inserted");
+    let patch = serde_json::json!({
+        "ops": [
+            { "op": "add", "path": "/layout/objects/0", "value": inserted },
+            { "op": "replace", "path": "/layout/objects/[id=obj_base_sink_600]/transform_mm/position_mm/x", "value": 1400 },
+            { "op": "remove", "path": "/layout/objects/[id=obj_inserted]" }
+        ]
+    });
+
+    let response = apply_patch_with_inverse_json(kitchen_state_fixture(), patch.to_string());
+    let value: serde_json::Value = serde_json::from_str(&response).unwrap();
+    let state = &value["kitchen_state"];
+    assert_eq!(object_ids(state), vec!["obj_base_sink_600"]);
+    assert_eq!(state["layout"]["objects"][0]["transform_mm"]["position_mm"]["x"], 1400);
+    let inverse_paths: Vec<&str> = value["inverse_patch"]["ops"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|op| op["path"].as_str().unwrap())
+        .collect();
+    assert!(inverse_paths.iter().all(|path| !path.contains("[id=")));
+
+    let missing = serde_json::json!({
+        "ops": [{ "op": "remove", "path": "/room/openings/[id=door_404]" }]
+    });
+    let response = apply_patch_json(kitchen_state_fixture(), missing.to_string());
+    let value: serde_json::Value = serde_json::from_str(&response).unwrap();
+    assert_eq!(value["violations"][0]["code"], "patch.path_not_found");
+    assert_eq!(value["violations"][0]["details"]["reason"], "pointer_id_not_found");
+}
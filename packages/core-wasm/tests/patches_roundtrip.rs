@@ -1,4 +1,45 @@
+use planforge_core_wasm::apply_patch_json;
+
+fn kitchen_state_fixture() -> serde_json::Value {
+    serde_json::json!({
+        "schema_version": "0.1.0",
+        "project": { "project_id": "p1", "revision_id": "r1", "units": "mm" },
+        "room": {
+            "size_mm": { "width": 3200, "depth": 2600, "height": 2700 },
+            "openings": [],
+            "utilities": [],
+            "restricted_zones": []
+        },
+        "layout": {
+            "objects": [
+                {
+                    "id": "obj_a",
+                    "kind": "module",
+                    "catalog_item_id": "base_sink_600",
+                    "transform_mm": { "position_mm": { "x": 0, "y": 0 }, "rotation_deg": 0 },
+                    "dims_mm": { "width": 600, "depth": 600, "height": 720 },
+                    "material_slots": {}
+                }
+            ]
+        },
+        "catalog_refs": { "modules_catalog_version": "modules_demo_0.1.0", "materials_catalog_version": "materials_demo_0.1.0" }
+    })
+}
+
 #[test]
 fn patches_roundtrip() {
-    assert!(true);
+    let state = kitchen_state_fixture();
+    let mut added = state["layout"]["objects"][0].clone();
+    added["id"] = serde_json::json!("obj_b");
+    let patch = serde_json::json!({
+        "ops": [
+            { "op": "add", "path": "/layout/objects/0", "value": added },
+            { "op": "move", "from": "/layout/objects/0", "path": "/layout/objects/-" },
+            { "op": "remove", "path": "/layout/objects/1" }
+        ]
+    });
+
+    let updated = apply_patch_json(state.to_string(), patch.to_string());
+    let value: serde_json::Value = serde_json::from_str(&updated).unwrap();
+    assert_eq!(value, state);
 }
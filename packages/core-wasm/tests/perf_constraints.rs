@@ -1,15 +1,10 @@
 use planforge_core_wasm::validate_layout_json;
 
-#[test]
-fn constraints_perf_budget() {
-    if std::env::var("PLANFORGE_PERF").ok().as_deref() != Some("1") {
-        return;
-    }
-
+fn grid_state(count: usize, row_limit_mm: i32, room_mm: i32) -> serde_json::Value {
     let mut objects = Vec::new();
     let mut x = 0;
     let mut y = 0;
-    for i in 0..200 {
+    for i in 0..count {
         objects.push(serde_json::json!({
             "id": format!("obj_{}", i),
             "kind": "module",
@@ -19,25 +14,46 @@ fn constraints_perf_budget() {
             "material_slots": {}
         }));
         x += 520;
-        if x > 3000 {
+        if x > row_limit_mm {
             x = 0;
             y += 520;
         }
     }
 
-    let state = serde_json::json!({
+    serde_json::json!({
         "schema_version": "0.1.0",
         "project": { "project_id": "p1", "revision_id": "r1", "units": "mm" },
         "room": {
-            "size_mm": { "width": 8000, "depth": 8000, "height": 2700 },
+            "size_mm": { "width": room_mm, "depth": room_mm, "height": 2700 },
             "openings": [],
             "utilities": [],
             "restricted_zones": []
         },
         "layout": { "objects": objects },
         "catalog_refs": { "modules_catalog_version": "modules_demo_0.1.0", "materials_catalog_version": "materials_demo_0.1.0" }
-    });
+    })
+}
+
+#[test]
+fn constraints_perf_budget() {
+    if std::env::var("PLANFORGE_PERF").ok().as_deref() != Some("1") {
+        return;
+    }
+
+    let state = grid_state(200, 3000, 8000);
+    let start = std::time::Instant::now();
+    let _ = validate_layout_json(state.to_string());
+    let elapsed = start.elapsed().as_millis();
+    assert!(elapsed < 200, "constraints perf budget exceeded: {}ms", elapsed);
+}
+
+#[test]
+fn constraints_perf_budget_large_plan() {
+    if std::env::var("PLANFORGE_PERF").ok().as_deref() != Some("1") {
+        return;
+    }
 
+    let state = grid_state(1000, 20000, 21000);
     let start = std::time::Instant::now();
     let _ = validate_layout_json(state.to_string());
     let elapsed = start.elapsed().as_millis();
@@ -0,0 +1,117 @@
+use planforge_core_wasm::{derive_render_model_json, validate_layout_json, PlanSession};
+
+fn state() -> serde_json::Value {
+    serde_json::json!({
+        "schema_version": "0.1.0",
+        "project": { "project_id": "p1", "revision_id": "r1", "units": "mm" },
+        "room": {
+            "size_mm": { "width": 3200, "depth": 2600, "height": 2700 },
+            "openings": [],
+            "utilities": [],
+            "restricted_zones": []
+        },
+        "layout": {
+            "objects": [
+                {
+                    "id": "obj_a",
+                    "kind": "module",
+                    "catalog_item_id": "base_sink_600",
+                    "transform_mm": { "position_mm": { "x": 0, "y": 0 }, "rotation_deg": 0 },
+                    "dims_mm": { "width": 600, "depth": 600, "height": 720 },
+                    "material_slots": {}
+                },
+                {
+                    "id": "obj_b",
+                    "kind": "module",
+                    "catalog_item_id": "base_drawers_800",
+                    "transform_mm": { "position_mm": { "x": 2000, "y": 0 }, "rotation_deg": 0 },
+                    "dims_mm": { "width": 600, "depth": 600, "height": 720 },
+                    "material_slots": {}
+                }
+            ]
+        },
+        "catalog_refs": { "modules_catalog_version": "modules_demo_0.1.0", "materials_catalog_version": "materials_demo_0.1.0" }
+    })
+}
+
+fn parse(json: String) -> serde_json::Value {
+    serde_json::from_str(&json).unwrap()
+}
+
+#[test]
+fn session_requires_loaded_state() {
+    let session = PlanSession::new();
+    assert_eq!(parse(session.validate())["violations"][0]["code"], "session.not_loaded");
+}
+
+#[test]
+fn session_drag_updates_validation_and_render_model() {
+    let mut session = PlanSession::new();
+    assert_eq!(parse(session.load(state().to_string()))["violations"], serde_json::json!([]));
+
+    let render = parse(session.render_model("draft".to_string()));
+    assert_eq!(render["nodes"][1]["transform"]["position_m"]["x"], 2.0);
+
+    assert_eq!(parse(session.set_object_position("obj_b".to_string(), 300, 0))["violations"], serde_json::json!([]));
+    let render = parse(session.render_model("draft".to_string()));
+    assert_eq!(render["nodes"][1]["transform"]["position_m"]["x"], 0.3);
+
+    let violations = parse(session.validate());
+    let expected = parse(validate_layout_json(session.state_json()));
+    assert_eq!(violations, expected);
+    assert_eq!(violations["violations"][0]["code"], "layout.collision");
+
+    let unknown = parse(session.set_object_position("obj_missing".to_string(), 0, 0));
+    assert_eq!(unknown["violations"][0]["code"], "session.unknown_object");
+}
+
+#[test]
+fn session_patch_returns_inverse_and_stays_atomic() {
+    let mut session = PlanSession::new();
+    session.load(state().to_string());
+
+    let patch = serde_json::json!({ "ops": [{ "op": "remove", "path": "/layout/objects/[id=obj_a]" }] });
+    let applied = parse(session.apply_patch(patch.to_string()));
+    assert_eq!(parse(session.room_metrics())["metrics"]["object_count"], 1);
+
+    session.apply_patch(applied["inverse_patch"].to_string());
+    assert_eq!(parse(session.state_json()), state());
+
+    let failing = serde_json::json!({
+        "ops": [
+            { "op": "remove", "path": "/layout/objects/0" },
+            { "op": "test", "path": "/schema_version", "value": "9.9.9" }
+        ]
+    });
+    let rejected = parse(session.apply_patch(failing.to_string()));
+    assert_eq!(rejected["violations"][0]["details"]["op_index"], 1);
+    assert_eq!(parse(session.state_json()), state());
+}
+
+#[test]
+fn session_patch_keeps_render_model_and_drags_in_sync() {
+    let mut session = PlanSession::new();
+    session.load(state().to_string());
+    session.render_model("draft".to_string());
+    session.set_object_position("obj_a".to_string(), 100, 0);
+
+    let patch = serde_json::json!({
+        "ops": [
+            { "op": "test", "path": "/layout/objects/[id=obj_a]/transform_mm/position_mm/x", "value": 100 },
+            { "op": "replace", "path": "/layout/objects/[id=obj_b]/catalog_item_id", "value": "base_oven_600" },
+            { "op": "replace", "path": "/layout/objects/[id=obj_b]/transform_mm/position_mm/x", "value": 1200 }
+        ]
+    });
+    assert_eq!(parse(session.apply_patch(patch.to_string()))["violations"], serde_json::json!([]));
+    let expected = parse(derive_render_model_json(session.state_json(), "draft".to_string()));
+    let render = parse(session.render_model("draft".to_string()));
+    assert_eq!(render["nodes"], expected["nodes"]);
+    assert_eq!(render["nodes"][1]["transform"]["position_m"]["x"], 1.2);
+    assert!(render["assets"]["gltf"]["base_oven_600"].is_object());
+
+    let invalid = serde_json::json!({ "ops": [{ "op": "replace", "path": "/layout/objects/0/dims_mm", "value": "wide" }] });
+    assert_eq!(parse(session.apply_patch(invalid.to_string()))["violations"][0]["code"], "json.parse_error");
+    assert_eq!(parse(session.state_json())["layout"]["objects"][0]["dims_mm"]["width"], 600);
+    let check = serde_json::json!({ "ops": [{ "op": "test", "path": "/layout/objects/0/dims_mm/width", "value": 600 }] });
+    assert_eq!(parse(session.apply_patch(check.to_string()))["violations"], serde_json::json!([]));
+}
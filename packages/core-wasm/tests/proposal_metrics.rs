@@ -0,0 +1,165 @@
+use planforge_core_wasm::{compute_proposal_metrics_json, validate_layout_with_rulesets_json};
+
+fn module(id: &str, x: i32, y: i32, tag: &str) -> serde_json::Value {
+    serde_json::json!({
+        "id": id,
+        "kind": "module",
+        "catalog_item_id": "base_600",
+        "transform_mm": { "position_mm": { "x": x, "y": y }, "rotation_deg": 0 },
+        "dims_mm": { "width": 600, "depth": 600, "height": 720 },
+        "material_slots": {},
+        "tags": [tag]
+    })
+}
+
+/// Sink and hob along the south wall, fridge 1400 mm in front of the sink, and a door in the
+/// north wall at x = 2000..2900.
+fn state(fridge_x: i32) -> serde_json::Value {
+    serde_json::json!({
+        "schema_version": "0.1.0",
+        "project": { "project_id": "p1", "revision_id": "r1", "units": "mm" },
+        "room": {
+            "size_mm": { "width": 4000, "depth": 3000, "height": 2700 },
+            "openings": [
+                { "id": "door_1", "kind": "door", "wall_id": "north", "offset_mm": 2000, "width_mm": 900, "height_mm": 2100 }
+            ],
+            "utilities": [
+                { "id": "water_1", "kind": "water", "position_mm": { "x": 300, "y": 0 }, "zone_radius_mm": 500 },
+                { "id": "vent_1", "kind": "vent", "position_mm": { "x": 1200, "y": 3000 }, "zone_radius_mm": 600 }
+            ],
+            "restricted_zones": []
+        },
+        "layout": {
+            "objects": [
+                module("obj_sink", 0, 0, "sink"),
+                module("obj_hob", 1200, 0, "hob"),
+                module("obj_fridge", fridge_x, 2000, "fridge")
+            ]
+        },
+        "catalog_refs": { "modules_catalog_version": "modules_demo_0.1.0", "materials_catalog_version": "materials_demo_0.1.0" }
+    })
+}
+
+fn compute(state: &serde_json::Value) -> serde_json::Value {
+    serde_json::from_str(&compute_proposal_metrics_json(state.to_string())).unwrap()
+}
+
+#[test]
+fn metrics_measure_the_layout() {
+    let response = compute(&state(0));
+    let metrics = &response["metrics"];
+
+    assert_eq!(metrics["schema_version"], "0.1.0");
+    assert_eq!(metrics["triangle_edges_mm"], serde_json::json!([1200, 2332, 2000]));
+    assert_eq!(metrics["triangle_proxy_mm"], 5532);
+    // The fridge stands 400 mm from the north wall; sink and hob are 600 mm apart.
+    assert_eq!(metrics["min_passage_mm"], 400);
+    assert_eq!(metrics["door_clearance_ok"], true);
+    // The sink is within the water zone (1.0); the hob is 3000 mm from a 600 mm vent zone (0.2).
+    assert_eq!(metrics["utility_fit_score"], 0.6);
+    assert_eq!(response["violations"], serde_json::json!([]));
+}
+
+#[test]
+fn blocked_door_is_reported() {
+    let response = compute(&state(2000));
+    assert_eq!(response["metrics"]["door_clearance_ok"], false);
+}
+
+#[test]
+fn metrics_are_deterministic() {
+    let state = state(0);
+    assert_eq!(
+        compute_proposal_metrics_json(state.to_string()),
+        compute_proposal_metrics_json(state.to_string())
+    );
+}
+
+#[test]
+fn incomplete_triangle_is_reported() {
+    let mut state = state(0);
+    state["layout"]["objects"].as_array_mut().unwrap().pop();
+
+    let response = compute(&state);
+    assert_eq!(response["metrics"]["triangle_proxy_mm"], 0);
+    assert!(response["metrics"].get("triangle_edges_mm").is_none());
+    let violation = &response["violations"][0];
+    assert_eq!(violation["code"], "metrics.work_triangle_incomplete");
+    assert_eq!(violation["severity"], "info");
+    assert_eq!(violation["details"]["missing"], serde_json::json!(["fridge"]));
+}
+
+#[test]
+fn invalid_json_has_no_metrics() {
+    let response: serde_json::Value = serde_json::from_str(&compute_proposal_metrics_json("{".to_string())).unwrap();
+    assert!(response["metrics"].is_null());
+    assert_eq!(response["violations"][0]["code"], "json.parse_error");
+}
+
+#[test]
+fn wide_walkway_is_measured() {
+    let mut state = state(0);
+    state["room"]["size_mm"]["depth"] = serde_json::json!(3100);
+    state["room"]["openings"] = serde_json::json!([]);
+    let run = |id: &str, y: i32| {
+        serde_json::json!({
+            "id": id,
+            "kind": "module",
+            "catalog_item_id": "base_run_4000",
+            "transform_mm": { "position_mm": { "x": 0, "y": y }, "rotation_deg": 0 },
+            "dims_mm": { "width": 4000, "depth": 600, "height": 900 },
+            "material_slots": {}
+        })
+    };
+    state["layout"]["objects"] = serde_json::json!([run("obj_south", 0), run("obj_north", 2500)]);
+
+    assert_eq!(compute(&state)["metrics"]["min_passage_mm"], 1900);
+}
+
+#[test]
+fn min_passage_matches_an_unbounded_search() {
+    // Validating against a ruleset whose minimum passage exceeds the room reports every walkway.
+    let rulesets = serde_json::json!([
+        { "version": "ruleset_unbounded", "min_passage_mm": 100000, "min_wall_clearance_mm": 0, "default_door_swing_mm": 900 }
+    ]);
+    let mut seed: u64 = 0x2545_f491_4f6c_dd1d;
+    let mut next = |range: i32| {
+        seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        ((seed >> 33) % range as u64) as i32
+    };
+
+    for _ in 0..200 {
+        let mut state = state(0);
+        state["room"]["size_mm"]["depth"] = serde_json::json!(3100);
+        let count = 2 + next(6);
+        let objects: Vec<serde_json::Value> = (0..count)
+            .map(|i| {
+                serde_json::json!({
+                    "id": format!("obj_{}", i),
+                    "kind": "module",
+                    "catalog_item_id": "base_600",
+                    "transform_mm": {
+                        "position_mm": { "x": next(3600), "y": next(2700) },
+                        "rotation_deg": ([0, 90, 30])[next(3) as usize]
+                    },
+                    "dims_mm": { "width": 300 + next(1500), "depth": 300 + next(600), "height": 900 },
+                    "material_slots": {}
+                })
+            })
+            .collect();
+        state["layout"]["objects"] = serde_json::json!(objects);
+
+        let metric = compute(&state)["metrics"]["min_passage_mm"].as_i64().unwrap();
+        state["project"]["ruleset_version"] = serde_json::json!("ruleset_unbounded");
+        let response: serde_json::Value =
+            serde_json::from_str(&validate_layout_with_rulesets_json(state.to_string(), rulesets.to_string())).unwrap();
+        let unbounded = response["violations"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter(|v| v["code"] == "layout.min_passage")
+            .filter_map(|v| v["details"]["width_mm"].as_i64())
+            .fold(3100, i64::min);
+        assert_eq!(metric, unbounded, "{}", state);
+    }
+}
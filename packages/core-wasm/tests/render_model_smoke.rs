@@ -97,3 +97,14 @@ fn derive_render_model_quality_lod() {
     let first_asset = assets.get("base_sink_600").and_then(|v| v.get("uri")).and_then(|v| v.as_str()).unwrap_or("");
     assert!(first_asset.contains("lod0"), "quality uses lod0");
 }
+
+#[test]
+fn elevated_objects_render_at_their_height() {
+    let mut state: serde_json::Value = serde_json::from_str(&kitchen_state_fixture()).unwrap();
+    state["layout"]["objects"][1]["transform_mm"]["elevation_mm"] = serde_json::json!(1400);
+    let output = derive_render_model_json(state.to_string(), "draft".to_string());
+    let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
+    let nodes = parsed["nodes"].as_array().unwrap();
+    assert_eq!(nodes[0]["transform"]["position_m"]["y"], 0.0);
+    assert_eq!(nodes[1]["transform"]["position_m"]["y"], 1.4);
+}
@@ -0,0 +1,170 @@
+use planforge_core_wasm::{compute_room_metrics_json, normalize_state_json, validate_layout_json};
+
+/// L-shaped room: 4000 wide along the south wall, with the north-east 2000 x 2000 corner cut out.
+fn l_shaped_state() -> serde_json::Value {
+    serde_json::json!({
+        "schema_version": "0.1.0",
+        "project": { "project_id": "p1", "revision_id": "r1", "units": "mm" },
+        "room": {
+            "size_mm": { "width": 4000, "depth": 4000, "height": 2700 },
+            "outline_mm": [
+                { "id": "s1", "start_mm": { "x": 0, "y": 0 } },
+                { "id": "e1", "start_mm": { "x": 4000, "y": 0 } },
+                { "id": "n1", "start_mm": { "x": 4000, "y": 2000 } },
+                { "id": "e2", "start_mm": { "x": 2000, "y": 2000 } },
+                { "id": "n2", "start_mm": { "x": 2000, "y": 4000 } },
+                { "id": "w1", "start_mm": { "x": 0, "y": 4000 } }
+            ],
+            "openings": [
+                {
+                    "id": "door_1",
+                    "kind": "door",
+                    "wall_id": "e2",
+                    "offset_mm": 500,
+                    "width_mm": 900,
+                    "height_mm": 2100,
+                    "swing": { "direction": "left", "radius_mm": 900 }
+                }
+            ],
+            "utilities": [
+                { "id": "water_1", "kind": "water", "wall_id": "n2", "offset_mm": 500, "zone_radius_mm": 800 }
+            ],
+            "restricted_zones": []
+        },
+        "layout": { "objects": [] },
+        "catalog_refs": { "modules_catalog_version": "modules_demo_0.1.0", "materials_catalog_version": "materials_demo_0.1.0" }
+    })
+}
+
+fn module(id: &str, x: i32, y: i32, tags: &[&str]) -> serde_json::Value {
+    serde_json::json!({
+        "id": id,
+        "kind": "module",
+        "catalog_item_id": "base_600",
+        "transform_mm": { "position_mm": { "x": x, "y": y }, "rotation_deg": 0 },
+        "dims_mm": { "width": 600, "depth": 600, "height": 720 },
+        "material_slots": {},
+        "tags": tags
+    })
+}
+
+fn violations(state: &serde_json::Value) -> Vec<(String, Vec<String>)> {
+    let response = validate_layout_json(state.to_string());
+    let value: serde_json::Value = serde_json::from_str(&response).unwrap();
+    value["violations"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|v| {
+            let ids = v["object_ids"].as_array().unwrap().iter().map(|id| id.as_str().unwrap().to_string()).collect();
+            (v["code"].as_str().unwrap().to_string(), ids)
+        })
+        .collect()
+}
+
+fn has(violations: &[(String, Vec<String>)], code: &str, id: &str) -> bool {
+    violations.iter().any(|(c, ids)| c == code && ids.iter().any(|i| i == id))
+}
+
+#[test]
+fn bounds_follow_the_outline() {
+    let mut state = l_shaped_state();
+    state["layout"]["objects"] = serde_json::json!([
+        module("obj_inside", 2500, 1000, &[]),
+        module("obj_in_cutout", 2500, 2500, &[]),
+        module("obj_across_corner", 1700, 1700, &[]),
+    ]);
+    let found = violations(&state);
+    assert!(!has(&found, "layout.out_of_bounds", "obj_inside"));
+    assert!(has(&found, "layout.out_of_bounds", "obj_in_cutout"));
+    assert!(has(&found, "layout.out_of_bounds", "obj_across_corner"));
+    assert!(!found.iter().any(|(c, _)| c == "room.invalid_outline"));
+}
+
+#[test]
+fn openings_and_utilities_sit_on_outline_walls() {
+    let mut state = l_shaped_state();
+    state["layout"]["objects"] = serde_json::json!([
+        module("obj_door", 1200, 2600, &[]),
+        module("obj_sink", 1400, 3400, &["sink"]),
+    ]);
+    let found = violations(&state);
+    // The door on `e2` swings west into the room, over x 1100..2000 and y 2500..3400.
+    assert!(has(&found, "layout.door_clearance", "obj_door"));
+    assert!(!has(&found, "layout.door_clearance", "obj_sink"));
+    // Water on `n2` is at (1500, 4000), measured from that wall's start at (2000, 4000).
+    assert!(!has(&found, "layout.sink_near_water", "obj_sink"));
+}
+
+#[test]
+fn self_intersecting_outline_is_rejected() {
+    let mut state = l_shaped_state();
+    state["room"]["outline_mm"] = serde_json::json!([
+        { "id": "a", "start_mm": { "x": 0, "y": 0 } },
+        { "id": "b", "start_mm": { "x": 2000, "y": 2000 } },
+        { "id": "c", "start_mm": { "x": 2000, "y": 0 } },
+        { "id": "d", "start_mm": { "x": 0, "y": 2000 } }
+    ]);
+    assert!(violations(&state).iter().any(|(c, _)| c == "room.invalid_outline"));
+}
+
+#[test]
+fn metrics_use_outline_area_and_walls() {
+    let response = compute_room_metrics_json(l_shaped_state().to_string());
+    let value: serde_json::Value = serde_json::from_str(&response).unwrap();
+    let metrics = &value["metrics"];
+    assert_eq!(metrics["room_area_mm2"], 12_000_000);
+    assert_eq!(metrics["room_perimeter_mm"], 16_000);
+    assert_eq!(metrics["wall_available_mm"]["e2"], 1_100);
+    assert_eq!(metrics["wall_available_mm"]["s1"], 4_000);
+}
+
+#[test]
+fn normalize_clamps_offsets_to_outline_walls() {
+    let mut state = l_shaped_state();
+    state["room"]["openings"][0]["offset_mm"] = serde_json::json!(1500);
+    let normalized: serde_json::Value = serde_json::from_str(&normalize_state_json(state.to_string())).unwrap();
+    assert_eq!(normalized["room"]["openings"][0]["offset_mm"], 1_100);
+    assert_eq!(normalized["room"]["outline_mm"].as_array().unwrap().len(), 6);
+}
+
+#[test]
+fn rectangular_shorthand_keeps_compass_walls() {
+    let mut state = l_shaped_state();
+    state["room"].as_object_mut().unwrap().remove("outline_mm");
+    state["room"]["openings"] = serde_json::json!([]);
+    state["room"]["utilities"] = serde_json::json!([]);
+    let response = compute_room_metrics_json(state.to_string());
+    let value: serde_json::Value = serde_json::from_str(&response).unwrap();
+    let walls = value["metrics"]["wall_available_mm"].as_object().unwrap();
+    let mut ids: Vec<&str> = walls.keys().map(String::as_str).collect();
+    ids.sort();
+    assert_eq!(ids, ["east", "north", "south", "west"]);
+    assert_eq!(value["metrics"]["room_area_mm2"], 16_000_000);
+}
+
+#[test]
+fn chimney_breast_between_corners_is_out_of_bounds() {
+    let mut state = l_shaped_state();
+    // Breast 400 wide projecting 400 from the south wall; the cabinet's corners all lie in the
+    // room but the breast cuts into its back edge.
+    state["room"]["outline_mm"] = serde_json::json!([
+        { "id": "s1", "start_mm": { "x": 0, "y": 0 } },
+        { "id": "b1", "start_mm": { "x": 1000, "y": 0 } },
+        { "id": "b2", "start_mm": { "x": 1000, "y": 400 } },
+        { "id": "b3", "start_mm": { "x": 1400, "y": 400 } },
+        { "id": "s2", "start_mm": { "x": 1400, "y": 0 } },
+        { "id": "e1", "start_mm": { "x": 3000, "y": 0 } },
+        { "id": "n1", "start_mm": { "x": 3000, "y": 3000 } },
+        { "id": "w1", "start_mm": { "x": 0, "y": 3000 } }
+    ]);
+    state["room"]["openings"] = serde_json::json!([]);
+    state["room"]["utilities"] = serde_json::json!([]);
+    state["layout"]["objects"] = serde_json::json!([
+        module("obj_over_breast", 900, 100, &[]),
+        module("obj_beside_breast", 400, 0, &[]),
+    ]);
+    let found = violations(&state);
+    assert!(has(&found, "layout.out_of_bounds", "obj_over_breast"));
+    assert!(!has(&found, "layout.out_of_bounds", "obj_beside_breast"));
+}
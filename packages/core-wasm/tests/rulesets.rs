@@ -0,0 +1,111 @@
+use planforge_core_wasm::constraints::ruleset::{RulesetCatalog, DEFAULT_RULESET_VERSION};
+use planforge_core_wasm::{validate_layout_json, validate_layout_with_rulesets_json};
+
+/// Two base units with a 1000 mm aisle between them.
+fn state(ruleset_version: Option<&str>) -> serde_json::Value {
+    let mut state = serde_json::json!({
+        "schema_version": "0.1.0",
+        "project": { "project_id": "p1", "revision_id": "r1", "units": "mm" },
+        "room": {
+            "size_mm": { "width": 3200, "depth": 2600, "height": 2700 },
+            "openings": [],
+            "utilities": [],
+            "restricted_zones": []
+        },
+        "layout": {
+            "objects": [
+                {
+                    "id": "obj_a",
+                    "kind": "module",
+                    "catalog_item_id": "base_600",
+                    "transform_mm": { "position_mm": { "x": 0, "y": 0 }, "rotation_deg": 0 },
+                    "dims_mm": { "width": 600, "depth": 600, "height": 720 },
+                    "material_slots": {}
+                },
+                {
+                    "id": "obj_b",
+                    "kind": "module",
+                    "catalog_item_id": "base_600",
+                    "transform_mm": { "position_mm": { "x": 0, "y": 1600 }, "rotation_deg": 0 },
+                    "dims_mm": { "width": 600, "depth": 600, "height": 720 },
+                    "material_slots": {}
+                }
+            ]
+        },
+        "catalog_refs": { "modules_catalog_version": "modules_demo_0.1.0", "materials_catalog_version": "materials_demo_0.1.0" }
+    });
+    if let Some(version) = ruleset_version {
+        state["project"]["ruleset_version"] = serde_json::json!(version);
+    }
+    state
+}
+
+fn codes(response: &str) -> Vec<String> {
+    let value: serde_json::Value = serde_json::from_str(response).unwrap();
+    value["violations"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|v| v["code"].as_str().unwrap().to_string())
+        .collect()
+}
+
+/// `code`, `object_ids` and `details.wall_id` of every violation.
+fn summary(response: &str) -> Vec<serde_json::Value> {
+    let value: serde_json::Value = serde_json::from_str(response).unwrap();
+    value["violations"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|v| serde_json::json!([v["code"], v["object_ids"], v["details"]["wall_id"]]))
+        .collect()
+}
+
+/// Violations that do not depend on the ruleset: obj_b stands free in the room, facing the north
+/// wall 400 mm away.
+fn obj_b_standing_free() -> Vec<serde_json::Value> {
+    vec![
+        serde_json::json!(["layout.min_passage", ["obj_b"], "north"]),
+        serde_json::json!(["layout.floating_object", ["obj_b"], null]),
+    ]
+}
+
+#[test]
+fn builtin_default_ruleset_is_available() {
+    let rulesets = RulesetCatalog::builtin();
+    let default = rulesets.get(DEFAULT_RULESET_VERSION).unwrap();
+    assert_eq!(default.min_passage_mm, 900);
+    assert_eq!(summary(&validate_layout_json(state(None).to_string())), obj_b_standing_free());
+}
+
+#[test]
+fn unknown_ruleset_is_an_error() {
+    let response = validate_layout_json(state(Some("ruleset_atlantis")).to_string());
+    let value: serde_json::Value = serde_json::from_str(&response).unwrap();
+    let violations = value["violations"].as_array().unwrap();
+    assert_eq!(violations.len(), 1);
+    assert_eq!(violations[0]["code"], "ruleset.unknown_version");
+    assert_eq!(violations[0]["severity"], "error");
+    assert_eq!(violations[0]["details"]["ruleset_version"], "ruleset_atlantis");
+    assert_eq!(violations[0]["details"]["known_versions"], serde_json::json!([DEFAULT_RULESET_VERSION]));
+}
+
+#[test]
+fn loaded_ruleset_changes_parameters() {
+    let rulesets = serde_json::json!([
+        { "version": "ruleset_wide_aisles", "min_passage_mm": 1200, "min_wall_clearance_mm": 0, "default_door_swing_mm": 900 }
+    ]);
+    let strict = validate_layout_with_rulesets_json(state(Some("ruleset_wide_aisles")).to_string(), rulesets.to_string());
+    let mut expected = vec![serde_json::json!(["layout.min_passage", ["obj_a", "obj_b"], null])];
+    expected.extend(obj_b_standing_free());
+    assert_eq!(summary(&strict), expected);
+
+    let default = validate_layout_with_rulesets_json(state(None).to_string(), rulesets.to_string());
+    assert_eq!(summary(&default), obj_b_standing_free());
+}
+
+#[test]
+fn invalid_rulesets_json_is_reported() {
+    let response = validate_layout_with_rulesets_json(state(None).to_string(), "[{\"version\": 1}]".to_string());
+    assert_eq!(codes(&response), ["json.parse_error"]);
+}
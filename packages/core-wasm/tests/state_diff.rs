@@ -0,0 +1,74 @@
+use planforge_core_wasm::{apply_patch_json, diff_state_json};
+
+fn object(id: &str, x: i32) -> serde_json::Value {
+    serde_json::json!({
+        "id": id,
+        "kind": "module",
+        "catalog_item_id": "base_600",
+        "transform_mm": { "position_mm": { "x": x, "y": 0 }, "rotation_deg": 0 },
+        "dims_mm": { "width": 600, "depth": 600, "height": 720 },
+        "material_slots": {}
+    })
+}
+
+fn state(objects: Vec<serde_json::Value>) -> serde_json::Value {
+    serde_json::json!({
+        "schema_version": "0.1.0",
+        "project": { "project_id": "p1", "revision_id": "r1", "units": "mm" },
+        "room": {
+            "size_mm": { "width": 5000, "depth": 3000, "height": 2700 },
+            "openings": [],
+            "utilities": [],
+            "restricted_zones": []
+        },
+        "layout": { "objects": objects },
+        "catalog_refs": { "modules_catalog_version": "modules_demo_0.1.0", "materials_catalog_version": "materials_demo_0.1.0" }
+    })
+}
+
+fn diff(before: &serde_json::Value, after: &serde_json::Value) -> serde_json::Value {
+    let response = diff_state_json(before.to_string(), after.to_string());
+    serde_json::from_str(&response).unwrap()
+}
+
+fn apply(state: &serde_json::Value, patch: &serde_json::Value) -> serde_json::Value {
+    let response = apply_patch_json(state.to_string(), patch.to_string());
+    serde_json::from_str(&response).unwrap()
+}
+
+#[test]
+fn identical_states_produce_empty_patch() {
+    let before = state(vec![object("a", 0), object("b", 600)]);
+    let patch = diff(&before, &before);
+    assert_eq!(patch["ops"], serde_json::json!([]));
+}
+
+#[test]
+fn reorder_is_a_single_move() {
+    let before = state(vec![object("a", 0), object("b", 600), object("c", 1200), object("d", 1800)]);
+    let after = state(vec![object("b", 600), object("c", 1200), object("d", 1800), object("a", 0)]);
+    let patch = diff(&before, &after);
+    assert_eq!(
+        patch["ops"],
+        serde_json::json!([{ "op": "move", "from": "/layout/objects/0", "path": "/layout/objects/3" }])
+    );
+    assert_eq!(apply(&before, &patch), after);
+}
+
+#[test]
+fn edits_are_field_level_and_matched_by_id() {
+    let before = state(vec![object("a", 0), object("b", 600), object("c", 1200)]);
+    let after = state(vec![object("c", 1300), object("a", 0), object("e", 2400)]);
+    let patch = diff(&before, &after);
+    let ops = patch["ops"].as_array().unwrap();
+    assert!(ops.iter().any(|op| op["op"] == "replace" && op["path"] == "/layout/objects/0/transform_mm/position_mm/x"));
+    assert!(ops.iter().all(|op| op["path"] != "/layout/objects"));
+    assert_eq!(apply(&before, &patch), after);
+}
+
+#[test]
+fn rejects_invalid_states() {
+    let response = diff_state_json("{}".to_string(), "nope".to_string());
+    let value: serde_json::Value = serde_json::from_str(&response).unwrap();
+    assert_eq!(value["violations"].as_array().unwrap().len(), 2);
+}
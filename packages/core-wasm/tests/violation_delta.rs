@@ -0,0 +1,60 @@
+use planforge_core_wasm::violation_delta_json;
+
+fn object(id: &str, x: i32) -> serde_json::Value {
+    serde_json::json!({
+        "id": id,
+        "kind": "module",
+        "catalog_item_id": "base_600",
+        "transform_mm": { "position_mm": { "x": x, "y": 0 }, "rotation_deg": 0 },
+        "dims_mm": { "width": 600, "depth": 600, "height": 720 },
+        "material_slots": {}
+    })
+}
+
+fn state(objects: Vec<serde_json::Value>) -> serde_json::Value {
+    serde_json::json!({
+        "schema_version": "0.1.0",
+        "project": { "project_id": "p1", "revision_id": "r1", "units": "mm" },
+        "room": {
+            "size_mm": { "width": 3200, "depth": 2600, "height": 2700 },
+            "openings": [],
+            "utilities": [],
+            "restricted_zones": []
+        },
+        "layout": { "objects": objects },
+        "catalog_refs": { "modules_catalog_version": "modules_demo_0.1.0", "materials_catalog_version": "materials_demo_0.1.0" }
+    })
+}
+
+fn codes(list: &serde_json::Value) -> Vec<&str> {
+    list.as_array().unwrap().iter().map(|v| v["code"].as_str().unwrap()).collect()
+}
+
+#[test]
+fn splits_violations_into_introduced_resolved_persisting() {
+    // before: a/b collide, c/d collide. after: a/b still collide (listed in reverse), c/d apart.
+    // Passages are also measured to walls and gaps of 50 mm or less need fillers instead, so
+    // moving d resolves its 400 mm passage to the east wall and introduces a filler gap on
+    // either side of it.
+    let before = state(vec![object("a", 0), object("b", 300), object("c", 1900), object("d", 2200)]);
+    let after = state(vec![object("b", 300), object("a", 0), object("c", 1900), object("d", 2550)]);
+
+    let response = violation_delta_json(before.to_string(), after.to_string());
+    let value: serde_json::Value = serde_json::from_str(&response).unwrap();
+
+    assert_eq!(codes(&value["persisting"]), vec!["layout.collision"]);
+    assert_eq!(codes(&value["introduced"]), vec!["layout.filler_required", "layout.filler_required"]);
+    assert_eq!(value["introduced"][0]["object_ids"], serde_json::json!(["c", "d"]));
+    assert_eq!(value["introduced"][1]["details"]["wall_id"], "east");
+    assert_eq!(codes(&value["resolved"]), vec!["layout.collision", "layout.min_passage"]);
+    assert_eq!(value["resolved"][0]["object_ids"], serde_json::json!(["c", "d"]));
+    assert_eq!(value["resolved"][1]["object_ids"], serde_json::json!(["d"]));
+    assert_eq!(value["resolved"][1]["details"]["wall_id"], "east");
+    assert_eq!(
+        value["violations_summary"],
+        serde_json::json!([
+            { "code": "layout.collision", "severity": "error", "count": 1 },
+            { "code": "layout.filler_required", "severity": "warning", "count": 2 }
+        ])
+    );
+}